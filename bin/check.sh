@@ -2,15 +2,25 @@
 
 set -uo pipefail
 
+# Pass --nightly to compare the `nightly` feature against the nightly std docs.
+nightly=""
+if [[ "${1:-}" == "--nightly" ]]; then
+    nightly="--nightly"
+fi
+
 check() {
     cargo run --quiet --manifest-path=tests/check/Cargo.toml --bin check $@
 }
 
 echo "Building docs"
-cargo doc --quiet --package gdvariants --no-deps
+if [[ -n "${nightly}" ]]; then
+    cargo +nightly doc --quiet --package gdvariants --no-deps --features nightly
+else
+    cargo doc --quiet --package gdvariants --no-deps --features rust_1_87
+fi
 
 mkdir -p ./tmp
 check list-sources | while read struct ; do
     echo "Comparing ${struct}"
-    check implements ${struct}
+    check implements ${struct} ${nightly}
 done
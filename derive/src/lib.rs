@@ -0,0 +1,278 @@
+//! Derive macros for [gdvariants](https://docs.rs/gdvariants).
+//!
+//! Use them through the `derive` feature of `gdvariants`, which re-exports
+//! them.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Error, Expr, Fields, Generics, Member, Type,
+    WherePredicate,
+};
+
+/// Implements the Godot traits of a newtype by delegating to the collection
+/// it wraps.
+///
+/// Generates `Export`, `FromVariant`, `ToVariant`, `Deref` and `DerefMut`.
+/// The struct must have exactly one field.
+///
+/// The `gd_collection` attribute takes:
+///
+/// * `hint = <expr>`: the export hint used when the property does not set
+///   one. Its type is the `Export::Hint` of the field.
+/// * `serde`: also generates `Serialize` and `Deserialize`. Requires the
+///   `serde` feature of `gdvariants`.
+///
+/// ```
+/// use gdnative::export::hint::{IntHint, RangeHint};
+/// use gdvariants::{hint::CollectionHint, vec::Vec, GdCollection};
+///
+/// #[derive(GdCollection)]
+/// #[gd_collection(serde, hint = CollectionHint::with_element(
+///     IntHint::Range(RangeHint::new(0, 99)),
+/// ))]
+/// struct Levels(Vec<i64>);
+/// ```
+#[proc_macro_derive(GdCollection, attributes(gd_collection))]
+pub fn derive_gd_collection(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    gd_collection(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+struct Options {
+    hint: Option<Expr>,
+    serde: bool,
+}
+
+fn options(input: &DeriveInput) -> Result<Options, Error> {
+    let mut options = Options {
+        hint: None,
+        serde: false,
+    };
+    for attr in &input.attrs {
+        if !attr.path().is_ident("gd_collection") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("serde") {
+                options.serde = true;
+                Ok(())
+            } else if meta.path.is_ident("hint") {
+                options.hint = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `hint` or `serde`"))
+            }
+        })?;
+    }
+    Ok(options)
+}
+
+/// Returns the only field of the struct and how to access it.
+fn field(input: &DeriveInput) -> Result<(Member, Type), Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "GdCollection can only be derived for structs",
+            ))
+        }
+    };
+    let field = match fields {
+        Fields::Named(named) if named.named.len() == 1 => &named.named[0],
+        Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => &unnamed.unnamed[0],
+        _ => {
+            return Err(Error::new_spanned(
+                fields,
+                "GdCollection requires a struct with exactly one field",
+            ))
+        }
+    };
+    let member = match &field.ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(0.into()),
+    };
+    Ok((member, field.ty.clone()))
+}
+
+/// Returns `generics` with `predicate` added to its where clause.
+fn with_bound(generics: &Generics, predicate: WherePredicate) -> Generics {
+    let mut generics = generics.clone();
+    generics.make_where_clause().predicates.push(predicate);
+    generics
+}
+
+fn gd_collection(input: DeriveInput) -> Result<TokenStream2, Error> {
+    let options = options(&input)?;
+    let (member, inner) = field(&input)?;
+    let name = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let private = quote!(::gdvariants::__private);
+
+    let export_info = match &options.hint {
+        Some(hint) => quote! {
+            <#inner as #private::gdnative::export::Export>::export_info(
+                ::std::option::Option::Some(hint.unwrap_or_else(|| #hint)),
+            )
+        },
+        None => quote! {
+            <#inner as #private::gdnative::export::Export>::export_info(hint)
+        },
+    };
+    let generics = with_bound(
+        &input.generics,
+        parse_quote!(#inner: #private::gdnative::export::Export),
+    );
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let export = quote! {
+        impl #impl_generics #private::gdnative::export::Export for #name #ty_generics #where_clause {
+            type Hint = <#inner as #private::gdnative::export::Export>::Hint;
+
+            fn export_info(
+                hint: ::std::option::Option<Self::Hint>,
+            ) -> #private::gdnative::export::ExportInfo {
+                #export_info
+            }
+        }
+    };
+
+    let generics = with_bound(
+        &input.generics,
+        parse_quote!(#inner: #private::gdnative::core_types::FromVariant),
+    );
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let from_variant = quote! {
+        impl #impl_generics #private::gdnative::core_types::FromVariant for #name #ty_generics #where_clause {
+            fn from_variant(
+                variant: &#private::gdnative::core_types::Variant,
+            ) -> ::std::result::Result<Self, #private::gdnative::core_types::FromVariantError> {
+                ::std::result::Result::Ok(Self {
+                    #member: <#inner as #private::gdnative::core_types::FromVariant>::from_variant(variant)?,
+                })
+            }
+        }
+    };
+
+    let generics = with_bound(
+        &input.generics,
+        parse_quote!(#inner: #private::gdnative::core_types::ToVariant),
+    );
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let to_variant = quote! {
+        impl #impl_generics #private::gdnative::core_types::ToVariant for #name #ty_generics #where_clause {
+            fn to_variant(&self) -> #private::gdnative::core_types::Variant {
+                <#inner as #private::gdnative::core_types::ToVariant>::to_variant(&self.#member)
+            }
+        }
+    };
+
+    let (impl_generics, _, where_clause) = input.generics.split_for_impl();
+    let deref = quote! {
+        impl #impl_generics ::std::ops::Deref for #name #ty_generics #where_clause {
+            type Target = #inner;
+
+            fn deref(&self) -> &#inner {
+                &self.#member
+            }
+        }
+
+        impl #impl_generics ::std::ops::DerefMut for #name #ty_generics #where_clause {
+            fn deref_mut(&mut self) -> &mut #inner {
+                &mut self.#member
+            }
+        }
+    };
+
+    let serde = if options.serde {
+        let generics = with_bound(
+            &input.generics,
+            parse_quote!(#inner: #private::serde::Serialize),
+        );
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+        let serialize = quote! {
+            impl #impl_generics #private::serde::Serialize for #name #ty_generics #where_clause {
+                fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+                where
+                    S: #private::serde::Serializer,
+                {
+                    <#inner as #private::serde::Serialize>::serialize(&self.#member, serializer)
+                }
+            }
+        };
+
+        let mut generics = with_bound(
+            &input.generics,
+            parse_quote!(#inner: #private::serde::Deserialize<'de>),
+        );
+        generics.params.insert(0, parse_quote!('de));
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+        let deserialize = quote! {
+            impl #impl_generics #private::serde::Deserialize<'de> for #name #ty_generics #where_clause {
+                fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+                where
+                    D: #private::serde::Deserializer<'de>,
+                {
+                    ::std::result::Result::Ok(Self {
+                        #member: <#inner as #private::serde::Deserialize<'de>>::deserialize(deserializer)?,
+                    })
+                }
+            }
+        };
+        quote!(#serialize #deserialize)
+    } else {
+        TokenStream2::new()
+    };
+
+    Ok(quote! {
+        #export
+        #from_variant
+        #to_variant
+        #deref
+        #serde
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+    use syn::parse_quote;
+
+    use crate::gd_collection;
+
+    fn contains(haystack: &proc_macro2::TokenStream, needle: proc_macro2::TokenStream) -> bool {
+        haystack.to_string().contains(&needle.to_string())
+    }
+
+    #[test]
+    fn test_hint_default() {
+        let output = gd_collection(parse_quote! {
+            #[gd_collection(hint = CollectionHint::default())]
+            struct Levels(Vec<i64>);
+        })
+        .unwrap();
+
+        assert!(contains(
+            &output,
+            quote! {
+                export_info(
+                    ::std::option::Option::Some(hint.unwrap_or_else(|| CollectionHint::default())),
+                )
+            }
+        ));
+    }
+
+    #[test]
+    fn test_hint_passed_through() {
+        let output = gd_collection(parse_quote! {
+            struct Levels(Vec<i64>);
+        })
+        .unwrap();
+
+        assert!(contains(&output, quote!(export_info(hint))));
+        assert!(!contains(&output, quote!(unwrap_or_else)));
+    }
+}
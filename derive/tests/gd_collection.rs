@@ -0,0 +1,84 @@
+use gdnative::{
+    core_types::{FromVariant, ToVariant},
+    export::{
+        hint::{IntHint, RangeHint},
+        Export,
+    },
+};
+use gdvariants::{
+    collections::{HashMap, HashSet},
+    hint::CollectionHint,
+    vec::Vec,
+    GdCollection,
+};
+
+#[derive(Debug, PartialEq, GdCollection)]
+#[gd_collection(serde)]
+struct Inventory(HashMap<i64, i64>);
+
+#[derive(GdCollection)]
+#[gd_collection(hint = CollectionHint::with_element(IntHint::Range(RangeHint::new(1, 10))))]
+struct Levels {
+    levels: Vec<i64>,
+}
+
+#[derive(GdCollection)]
+struct Tags<T>(HashSet<T>)
+where
+    T: std::hash::Hash + Eq;
+
+fn assert_godot<T: Export + FromVariant + ToVariant>() {}
+
+#[test]
+fn test_implements_godot_traits() {
+    assert_godot::<Inventory>();
+    assert_godot::<Levels>();
+    assert_godot::<Tags<i64>>();
+}
+
+fn export_info<T: Export>(hint: Option<T::Hint>) -> String {
+    format!("{:?}", T::export_info(hint))
+}
+
+#[test]
+#[ignore = "building an ExportInfo needs the Godot engine"]
+fn test_export_hint_default() {
+    let hint = || CollectionHint::with_element(IntHint::Range(RangeHint::new(1, 10)));
+    let other = || CollectionHint::with_element(IntHint::Range(RangeHint::new(0, 5)));
+
+    assert_eq!(
+        export_info::<Levels>(None),
+        export_info::<Vec<i64>>(Some(hint()))
+    );
+    assert_ne!(export_info::<Levels>(None), export_info::<Vec<i64>>(None));
+    assert_eq!(
+        export_info::<Levels>(Some(other())),
+        export_info::<Vec<i64>>(Some(other()))
+    );
+}
+
+#[test]
+fn test_deref() {
+    let mut inventory = Inventory(HashMap::new());
+    inventory.insert(7, 2);
+    *inventory.get_mut(&7).unwrap() += 1;
+
+    assert_eq!(inventory.len(), 1);
+    assert_eq!(inventory[&7], 3);
+
+    let mut levels = Levels { levels: Vec::new() };
+    levels.push(1);
+    assert_eq!(*levels, [1]);
+
+    let tags = Tags(HashSet::from(["boss"]));
+    assert!(tags.contains("boss"));
+}
+
+#[test]
+fn test_serde() {
+    let inventory = Inventory(HashMap::from([(7, 3)]));
+    let json = serde_json::to_string(&inventory).unwrap();
+
+    assert_eq!(json, "{\"7\":3}");
+    assert_eq!(serde_json::from_str::<Inventory>(&json).unwrap(), inventory);
+}
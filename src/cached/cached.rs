@@ -0,0 +1,240 @@
+use std::{
+    cell::RefCell,
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+use gdnative::{
+    core_types::{Dictionary, VariantArray, VariantDispatch},
+    export::{Export, ExportInfo},
+    object::ownership::Unique,
+    prelude::{FromVariant, FromVariantError, OwnedToVariant, ToVariant, Variant},
+};
+
+/// A wrapper that remembers the [`Variant`] produced by [`ToVariant`] until
+/// the wrapped value is mutated.
+///
+/// Converting a large collection rebuilds the whole Godot `Dictionary` or
+/// `Array` every time. `Cached` stores the result of the first conversion and
+/// hands out the same `Variant` until the value is borrowed mutably, through
+/// [`DerefMut`] or any method, operator or index that requires it.
+///
+/// Each call returns a deep copy of the cached `Dictionary` or `Array`, so a
+/// script that modifies an exported `Cached` property does not change the
+/// cache. Copying the `Variant`s is still cheaper than converting every
+/// element again.
+///
+/// # Examples
+///
+/// ```
+/// use gdvariants::{cached::Cached, collections::HashMap};
+///
+/// let mut players: Cached<HashMap<i64, String>> = Cached::default();
+/// players.insert(1, "Ada".to_string());
+///
+/// assert_eq!(players.len(), 1);
+/// assert!(!players.is_cached());
+/// ```
+pub struct Cached<T> {
+    value: T,
+    variant: RefCell<Option<Variant>>,
+}
+
+impl<T> Cached<T> {
+    /// Wraps `value`, starting with an empty cache.
+    #[inline]
+    pub fn new(value: T) -> Cached<T> {
+        Cached {
+            value,
+            variant: RefCell::new(None),
+        }
+    }
+
+    /// Unwraps the value, discarding the cache.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Returns `true` if the next call to [`to_variant`](ToVariant::to_variant)
+    /// will be served from the cache.
+    #[inline]
+    pub fn is_cached(&self) -> bool {
+        self.variant.borrow().is_some()
+    }
+
+    /// Discards the cached `Variant`.
+    #[inline]
+    pub fn invalidate(&mut self) {
+        self.variant.get_mut().take();
+    }
+}
+
+impl<T> Deref for Cached<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for Cached<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        self.invalidate();
+        &mut self.value
+    }
+}
+
+impl<T> Export for Cached<T>
+where
+    T: Export,
+{
+    type Hint = T::Hint;
+
+    fn export_info(hint: Option<Self::Hint>) -> ExportInfo {
+        T::export_info(hint)
+    }
+}
+
+impl<T> FromVariant for Cached<T>
+where
+    T: FromVariant,
+{
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        Ok(Cached::new(T::from_variant(variant)?))
+    }
+}
+
+impl<T> ToVariant for Cached<T>
+where
+    T: ToVariant,
+{
+    fn to_variant(&self) -> Variant {
+        duplicate(
+            self.variant
+                .borrow_mut()
+                .get_or_insert_with(|| self.value.to_variant()),
+        )
+    }
+}
+
+/// Copies the collections in `variant`, so the copy shares no `Dictionary` or
+/// `Array` with it.
+fn duplicate(variant: &Variant) -> Variant {
+    match variant.dispatch() {
+        VariantDispatch::Dictionary(dictionary) => {
+            let copy = Dictionary::new();
+            for (key, value) in dictionary.iter() {
+                copy.insert(key, duplicate(&value));
+            }
+            copy.owned_to_variant()
+        }
+        VariantDispatch::VariantArray(array) => array
+            .iter()
+            .map(|value| duplicate(&value))
+            .collect::<VariantArray<Unique>>()
+            .owned_to_variant(),
+        _ => variant.clone(),
+    }
+}
+
+impl<T> Clone for Cached<T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Cached<T> {
+        Cached::new(self.value.clone())
+    }
+}
+
+impl<T> fmt::Debug for Cached<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl<T> Default for Cached<T>
+where
+    T: Default,
+{
+    #[inline]
+    fn default() -> Cached<T> {
+        Cached::new(T::default())
+    }
+}
+
+impl<T> From<T> for Cached<T> {
+    #[inline]
+    fn from(value: T) -> Cached<T> {
+        Cached::new(value)
+    }
+}
+
+impl<T, U> PartialEq<Cached<U>> for Cached<T>
+where
+    T: PartialEq<U>,
+{
+    #[inline]
+    fn eq(&self, other: &Cached<U>) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T> Eq for Cached<T> where T: Eq {}
+
+#[cfg(test)]
+mod tests {
+    use gdnative::prelude::{FromVariant, ToVariant, VariantArray};
+
+    use crate::{cached::Cached, collections::HashMap, vec::Vec};
+
+    #[test]
+    fn test_deref() {
+        let cached = Cached::new(Vec::from(vec![2, 1, 3]));
+
+        assert_eq!(cached.len(), 3);
+        assert!(!cached.is_cached());
+    }
+
+    #[test]
+    fn test_deref_mut() {
+        let mut cached = Cached::new(HashMap::from([(2, 4), (1, 2)]));
+
+        cached.insert(3, 6);
+        cached.entry(1).and_modify(|value| *value = 0);
+
+        assert_eq!(*cached, HashMap::from([(2, 4), (1, 0), (3, 6)]));
+    }
+
+    #[test]
+    #[ignore = "creating a Variant needs the Godot engine"]
+    fn test_invalidate_on_deref_mut() {
+        let mut cached = Cached::new(Vec::from(vec![2, 1, 3]));
+
+        let variant = cached.to_variant();
+        assert!(cached.is_cached());
+        assert_eq!(cached.len(), 3);
+        assert!(cached.is_cached());
+
+        let copy = VariantArray::from_variant(&cached.to_variant()).unwrap();
+        unsafe { copy.assume_unique() }.push(5);
+        assert_eq!(cached.to_variant(), variant);
+
+        cached.push(4);
+        assert!(!cached.is_cached());
+        assert_ne!(cached.to_variant(), variant);
+        assert!(cached.is_cached());
+    }
+
+    #[test]
+    fn test_into_inner() {
+        let cached = Cached::new(Vec::from(vec![2, 1, 3]));
+
+        assert_eq!(cached.into_inner(), vec![2, 1, 3]);
+    }
+}
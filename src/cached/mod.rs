@@ -0,0 +1,7 @@
+#[allow(clippy::module_inception)]
+mod cached;
+
+#[cfg(feature = "serde")]
+pub mod serde;
+
+pub use cached::Cached;
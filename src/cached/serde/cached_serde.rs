@@ -0,0 +1,53 @@
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::cached::Cached;
+
+impl<'de, T> Deserialize<'de> for Cached<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Cached::new(T::deserialize(deserializer)?))
+    }
+}
+
+impl<T> Serialize for Cached<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        T::serialize(self, serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cached::Cached, vec::Vec};
+
+    #[test]
+    fn test_from_str() {
+        let json = "[2, 1, 3]".to_string();
+
+        let cratevec: Vec<i32> = serde_json::from_str(&json).unwrap();
+        let cached: Cached<Vec<i32>> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(cratevec, *cached);
+    }
+
+    #[test]
+    fn test_to_string() {
+        let cratevec = Vec::from(vec![2, 1, 3]);
+        let cached = Cached::new(Vec::from(vec![2, 1, 3]));
+
+        let crateres: String = serde_json::to_string(&cratevec).unwrap();
+        let cachedres: String = serde_json::to_string(&cached).unwrap();
+
+        assert_eq!(crateres, cachedres);
+    }
+}
@@ -0,0 +1,262 @@
+use std::{
+    fmt,
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+};
+
+use gdnative::{
+    core_types::{Dictionary, VariantType},
+    export::{Export, ExportInfo},
+    prelude::{FromVariant, FromVariantError, OwnedToVariant, ToVariant, Variant},
+};
+
+use crate::enums::GodotEnum;
+
+/// A map with one value for every variant of the enum `E`.
+///
+/// Values are stored densely, in the order of [`GodotEnum::VARIANTS`], and
+/// are accessed by indexing with a variant. The map converts to and from a
+/// Godot `Dictionary` keyed by the [variant names](GodotEnum::name). When
+/// exported, the default value shows one entry per variant in the inspector.
+///
+/// # Examples
+///
+/// ```
+/// use gdvariants::{collections::EnumMap, enums::GodotEnum};
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// enum Damage {
+///     Fire,
+///     Ice,
+/// }
+///
+/// impl GodotEnum for Damage {
+///     const VARIANTS: &'static [Self] = &[Damage::Fire, Damage::Ice];
+///
+///     fn name(self) -> &'static str {
+///         match self {
+///             Damage::Fire => "fire",
+///             Damage::Ice => "ice",
+///         }
+///     }
+/// }
+///
+/// let mut resistances: EnumMap<Damage, f64> = EnumMap::default();
+/// resistances[Damage::Ice] = 0.5;
+///
+/// assert_eq!(resistances[Damage::Fire], 0.0);
+/// assert_eq!(resistances[Damage::Ice], 0.5);
+/// ```
+pub struct EnumMap<E, V> {
+    values: std::vec::Vec<V>,
+    _marker: PhantomData<E>,
+}
+
+impl<E, V> EnumMap<E, V>
+where
+    E: GodotEnum,
+{
+    /// Creates a map by calling `f` for every variant, in order.
+    pub fn from_fn<F>(f: F) -> EnumMap<E, V>
+    where
+        F: FnMut(E) -> V,
+    {
+        EnumMap {
+            values: E::VARIANTS.iter().copied().map(f).collect(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of values, which is the number of variants of `E`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if `E` has no variants.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// An iterator visiting all variant-value pairs, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (E, &V)> {
+        E::VARIANTS.iter().copied().zip(self.values.iter())
+    }
+
+    /// An iterator visiting all variant-value pairs, in order, with mutable
+    /// references to the values.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (E, &mut V)> {
+        E::VARIANTS.iter().copied().zip(self.values.iter_mut())
+    }
+
+    /// Returns the values as a slice, in the order of the variants.
+    #[inline]
+    pub fn as_slice(&self) -> &[V] {
+        &self.values
+    }
+
+    /// Returns the values as a mutable slice, in the order of the variants.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [V] {
+        &mut self.values
+    }
+}
+
+impl<E, V> Index<E> for EnumMap<E, V>
+where
+    E: GodotEnum,
+{
+    type Output = V;
+
+    #[inline]
+    fn index(&self, key: E) -> &V {
+        &self.values[key.index()]
+    }
+}
+
+impl<E, V> IndexMut<E> for EnumMap<E, V>
+where
+    E: GodotEnum,
+{
+    #[inline]
+    fn index_mut(&mut self, key: E) -> &mut V {
+        &mut self.values[key.index()]
+    }
+}
+
+pub enum NoHint {}
+
+impl<E, V> Export for EnumMap<E, V>
+where
+    E: GodotEnum,
+    Self: ToVariant,
+{
+    type Hint = NoHint;
+
+    fn export_info(_hint: Option<Self::Hint>) -> ExportInfo {
+        ExportInfo::new(VariantType::Dictionary)
+    }
+}
+
+impl<E, V> FromVariant for EnumMap<E, V>
+where
+    E: GodotEnum,
+    V: FromVariant + Default,
+{
+    /// Reads a `Dictionary` keyed by variant names. Variants that are missing
+    /// from the dictionary get the default value, unknown names are rejected.
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        let dictionary = Dictionary::from_variant(variant)?;
+        let mut map: EnumMap<E, V> = EnumMap::default();
+        for (variant_key, variant_value) in dictionary.iter() {
+            let name = String::from_variant(&variant_key)?;
+            let key = E::from_name(&name).ok_or_else(|| {
+                FromVariantError::Custom(format!(
+                    "unknown enum variant {:?}, expected one of {:?}",
+                    name,
+                    E::names()
+                ))
+            })?;
+            map[key] = V::from_variant(&variant_value)?;
+        }
+        Ok(map)
+    }
+}
+
+impl<E, V> ToVariant for EnumMap<E, V>
+where
+    E: GodotEnum,
+    V: ToVariant,
+{
+    fn to_variant(&self) -> Variant {
+        let dictionary = Dictionary::new();
+        for (key, value) in self.iter() {
+            dictionary.insert(key.name(), value);
+        }
+        dictionary.owned_to_variant()
+    }
+}
+
+impl<E, V> Clone for EnumMap<E, V>
+where
+    V: Clone,
+{
+    fn clone(&self) -> EnumMap<E, V> {
+        EnumMap {
+            values: self.values.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<E, V> fmt::Debug for EnumMap<E, V>
+where
+    E: GodotEnum + fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<E, V> Default for EnumMap<E, V>
+where
+    E: GodotEnum,
+    V: Default,
+{
+    #[inline]
+    fn default() -> EnumMap<E, V> {
+        EnumMap::from_fn(|_| V::default())
+    }
+}
+
+impl<E, V> PartialEq for EnumMap<E, V>
+where
+    V: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &EnumMap<E, V>) -> bool {
+        self.values == other.values
+    }
+}
+
+impl<E, V> Eq for EnumMap<E, V> where V: Eq {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        collections::EnumMap,
+        enums::{tests::Element, GodotEnum},
+    };
+
+    #[test]
+    fn test_from_fn() {
+        let map: EnumMap<Element, usize> = EnumMap::from_fn(|element: Element| element.index() * 2);
+
+        assert_eq!(map.as_slice(), &[0, 2, 4]);
+    }
+
+    #[test]
+    fn test_index_mut() {
+        let mut map: EnumMap<Element, i32> = EnumMap::default();
+        map[Element::Water] = 5;
+
+        assert_eq!(map[Element::Fire], 0);
+        assert_eq!(map[Element::Water], 5);
+        assert_eq!(map.len(), 3);
+    }
+
+    #[test]
+    fn test_iter() {
+        let map: EnumMap<Element, usize> = EnumMap::from_fn(|element: Element| element.index());
+
+        let pairs: std::vec::Vec<(Element, usize)> =
+            map.iter().map(|(key, value)| (key, *value)).collect();
+
+        assert_eq!(
+            pairs,
+            vec![(Element::Fire, 0), (Element::Water, 1), (Element::Earth, 2)]
+        );
+    }
+}
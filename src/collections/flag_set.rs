@@ -0,0 +1,329 @@
+use std::{
+    fmt,
+    iter::FromIterator,
+    ops::{Deref, DerefMut},
+};
+
+use gdnative::{
+    export::{
+        hint::{EnumHint, IntHint},
+        Export, ExportInfo,
+    },
+    prelude::{FromVariant, FromVariantError, ToVariant, Variant},
+};
+
+use crate::{collections::HashSet, enums::GodotEnum};
+
+/// A set of enum variants that is exported as a flags property.
+///
+/// The inspector shows one checkbox per variant of `E`. The set converts to
+/// and from an integer bitmask [`Variant`], where the variant at position `i`
+/// of [`GodotEnum::VARIANTS`] is bit `i`. The [`HashSet`] methods, including
+/// the set operations, are available through [`Deref`] and [`DerefMut`].
+///
+/// `E` can have at most 64 variants, one per bit of the mask. Using a
+/// `FlagSet` of a wider enum fails to compile.
+///
+/// # Examples
+///
+/// ```
+/// use gdvariants::{collections::FlagSet, enums::GodotEnum};
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// enum Ability {
+///     Jump,
+///     Dash,
+///     Climb,
+/// }
+///
+/// impl GodotEnum for Ability {
+///     const VARIANTS: &'static [Self] = &[Ability::Jump, Ability::Dash, Ability::Climb];
+///
+///     fn name(self) -> &'static str {
+///         match self {
+///             Ability::Jump => "Jump",
+///             Ability::Dash => "Dash",
+///             Ability::Climb => "Climb",
+///         }
+///     }
+/// }
+///
+/// let mut abilities: FlagSet<Ability> = FlagSet::new();
+/// abilities.insert(Ability::Jump);
+/// abilities.insert(Ability::Climb);
+///
+/// assert_eq!(abilities.bits(), 0b101);
+/// assert_eq!(FlagSet::from_bits(0b101), Some(abilities));
+/// ```
+pub struct FlagSet<E> {
+    base: HashSet<E>,
+}
+
+impl<E> FlagSet<E>
+where
+    E: GodotEnum,
+{
+    /// Fails to evaluate if `E` has more variants than a bitmask has bits.
+    const FITS: () = assert!(
+        E::VARIANTS.len() <= 64,
+        "a FlagSet can hold at most 64 variants"
+    );
+
+    /// Creates an empty `FlagSet`.
+    #[inline]
+    #[must_use]
+    pub fn new() -> FlagSet<E> {
+        let () = Self::FITS;
+        FlagSet {
+            base: HashSet::new(),
+        }
+    }
+
+    /// Creates a `FlagSet` containing every variant of `E`.
+    #[inline]
+    pub fn all() -> FlagSet<E> {
+        let () = Self::FITS;
+        E::VARIANTS.iter().copied().collect()
+    }
+
+    /// Creates a `FlagSet` from a bitmask.
+    ///
+    /// Returns `None` if a bit is set that does not belong to a variant.
+    pub fn from_bits(bits: i64) -> Option<FlagSet<E>> {
+        let () = Self::FITS;
+        let bits = bits as u64;
+        if E::VARIANTS.len() < 64 && bits >> E::VARIANTS.len() != 0 {
+            return None;
+        }
+        Some(
+            E::VARIANTS
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| bits & (1 << index) != 0)
+                .map(|(_, variant)| *variant)
+                .collect(),
+        )
+    }
+
+    /// Returns the bitmask of the set.
+    pub fn bits(&self) -> i64 {
+        let () = Self::FITS;
+        self.base
+            .iter()
+            .fold(0u64, |bits, variant| bits | (1 << variant.index())) as i64
+    }
+}
+
+impl<E> Deref for FlagSet<E> {
+    type Target = HashSet<E>;
+
+    #[inline]
+    fn deref(&self) -> &HashSet<E> {
+        &self.base
+    }
+}
+
+impl<E> DerefMut for FlagSet<E> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut HashSet<E> {
+        &mut self.base
+    }
+}
+
+pub enum NoHint {}
+
+impl<E> Export for FlagSet<E>
+where
+    E: GodotEnum,
+{
+    type Hint = NoHint;
+
+    fn export_info(_hint: Option<Self::Hint>) -> ExportInfo {
+        let () = FlagSet::<E>::FITS;
+        IntHint::<i64>::Flags(EnumHint::new(E::names())).export_info()
+    }
+}
+
+impl<E> FromVariant for FlagSet<E>
+where
+    E: GodotEnum,
+{
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        let bits = i64::from_variant(variant)?;
+        FlagSet::from_bits(bits).ok_or_else(|| {
+            FromVariantError::Custom(format!(
+                "bitmask {:#b} has bits set beyond the {} known flags",
+                bits,
+                E::VARIANTS.len()
+            ))
+        })
+    }
+}
+
+impl<E> ToVariant for FlagSet<E>
+where
+    E: GodotEnum,
+{
+    fn to_variant(&self) -> Variant {
+        self.bits().to_variant()
+    }
+}
+
+impl<E> Clone for FlagSet<E>
+where
+    E: Clone,
+{
+    fn clone(&self) -> FlagSet<E> {
+        FlagSet {
+            base: self.base.clone(),
+        }
+    }
+}
+
+impl<E> fmt::Debug for FlagSet<E>
+where
+    E: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.base.fmt(f)
+    }
+}
+
+impl<E> Default for FlagSet<E>
+where
+    E: GodotEnum,
+{
+    #[inline]
+    fn default() -> FlagSet<E> {
+        FlagSet::new()
+    }
+}
+
+impl<E> PartialEq for FlagSet<E>
+where
+    E: GodotEnum,
+{
+    #[inline]
+    fn eq(&self, other: &FlagSet<E>) -> bool {
+        self.base == other.base
+    }
+}
+
+impl<E> Eq for FlagSet<E> where E: GodotEnum {}
+
+impl<E> From<HashSet<E>> for FlagSet<E> {
+    #[inline]
+    fn from(set: HashSet<E>) -> FlagSet<E> {
+        FlagSet { base: set }
+    }
+}
+
+impl<E> From<FlagSet<E>> for HashSet<E> {
+    #[inline]
+    fn from(set: FlagSet<E>) -> HashSet<E> {
+        set.base
+    }
+}
+
+impl<E> FromIterator<E> for FlagSet<E>
+where
+    E: GodotEnum,
+{
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = E>>(iter: I) -> FlagSet<E> {
+        FlagSet {
+            base: HashSet::from_iter(iter),
+        }
+    }
+}
+
+impl<E> Extend<E> for FlagSet<E>
+where
+    E: GodotEnum,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = E>>(&mut self, iter: I) {
+        self.base.extend(iter)
+    }
+}
+
+impl<'a, E> IntoIterator for &'a FlagSet<E> {
+    type Item = &'a E;
+    type IntoIter = std::collections::hash_set::Iter<'a, E>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.base.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        collections::{FlagSet, HashSet},
+        enums::{tests::Element, GodotEnum},
+    };
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    struct Wide(u8);
+
+    impl GodotEnum for Wide {
+        const VARIANTS: &'static [Self] = &{
+            let mut variants = [Wide(0); 64];
+            let mut index = 0;
+            while index < variants.len() {
+                variants[index] = Wide(index as u8);
+                index += 1;
+            }
+            variants
+        };
+
+        fn name(self) -> &'static str {
+            "Wide"
+        }
+    }
+
+    #[test]
+    fn test_bits() {
+        let set: FlagSet<Element> = [Element::Fire, Element::Earth].into_iter().collect();
+
+        assert_eq!(set.bits(), 0b101);
+        assert_eq!(FlagSet::<Element>::new().bits(), 0);
+        assert_eq!(FlagSet::<Element>::all().bits(), 0b111);
+    }
+
+    #[test]
+    fn test_from_bits() {
+        let set: FlagSet<Element> = FlagSet::from_bits(0b110).unwrap();
+
+        assert_eq!(*set, HashSet::from([Element::Water, Element::Earth]));
+    }
+
+    #[test]
+    fn test_from_bits_unknown() {
+        assert_eq!(FlagSet::<Element>::from_bits(0b1000), None);
+        assert_eq!(FlagSet::<Element>::from_bits(-1), None);
+    }
+
+    #[test]
+    fn test_from_bits_wide() {
+        let set = FlagSet::<Wide>::from_bits(-1).unwrap();
+
+        assert_eq!(set.len(), 64);
+        assert!(set.contains(&Wide(63)));
+        assert_eq!(set.bits(), -1);
+        assert_eq!(FlagSet::<Wide>::new().bits(), 0);
+        assert_eq!(FlagSet::<Wide>::from_bits(1 << 63).unwrap().bits(), 1 << 63);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a: FlagSet<Element> = FlagSet::from_bits(0b011).unwrap();
+        let b: FlagSet<Element> = FlagSet::from_bits(0b110).unwrap();
+
+        let both: FlagSet<Element> = FlagSet::from(&*a & &*b);
+
+        assert_eq!(both.bits(), 0b010);
+        assert!(a.contains(&Element::Fire));
+    }
+}
@@ -11,6 +11,8 @@ use gdnative::{
     prelude::{FromVariant, FromVariantError, OwnedToVariant, ToVariant, ToVariantEq, Variant},
 };
 
+use crate::{vec::Vec, with};
+
 /// A [hash map] implemented with quadratic probing and SIMD lookup.
 ///
 /// By default, `HashMap` uses a hashing algorithm selected to provide
@@ -210,6 +212,8 @@ where
 {
     type Hint = NoHint;
 
+    /// Exports an untyped `Dictionary`, since Godot 3 has no hint for the
+    /// keys or values of a dictionary.
     fn export_info(_hint: Option<Self::Hint>) -> ExportInfo {
         ExportInfo::new(gdnative::core_types::VariantType::Dictionary)
     }
@@ -222,15 +226,9 @@ where
     S: BuildHasher + Default,
 {
     fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
-        let dictionary = Dictionary::from_variant(variant)?;
-        let mut hash_map: HashMap<K, V, S> =
-            HashMap::with_capacity_and_hasher(dictionary.len() as usize, S::default());
-        for (variant_key, variant_value) in dictionary.iter() {
-            let key = K::from_variant(&variant_key)?;
-            let value = V::from_variant(&variant_value)?;
-            hash_map.insert(key, value);
-        }
-        Ok(hash_map)
+        Ok(HashMap {
+            base: with::hash_map::from_variant(variant)?,
+        })
     }
 }
 
@@ -240,8 +238,20 @@ where
     V: ToVariant,
 {
     fn to_variant(&self) -> Variant {
+        with::hash_map::to_variant(&self.base)
+    }
+}
+
+impl<K, V, S> HashMap<K, V, S>
+where
+    K: ToVariantEq + OwnedToVariant,
+    V: OwnedToVariant,
+{
+    /// Converts the map into a `Variant` holding a `Dictionary`, moving each
+    /// key and value into it instead of borrowing.
+    pub fn into_variant(self) -> Variant {
         let dictionary = Dictionary::new();
-        for (key, value) in &self.base {
+        for (key, value) in self.base {
             dictionary.insert(key, value);
         }
         dictionary.owned_to_variant()
@@ -260,18 +270,78 @@ impl<K, V> BorrowMut<std::collections::HashMap<K, V>> for HashMap<K, V> {
     }
 }
 
-impl<K, V> From<std::collections::HashMap<K, V>> for HashMap<K, V> {
-    fn from(map: std::collections::HashMap<K, V>) -> HashMap<K, V> {
+impl<K, V, S> HashMap<K, V, S> {
+    /// Returns the std map this map wraps, without copying.
+    #[inline]
+    pub fn into_inner(self) -> std::collections::HashMap<K, V, S> {
+        self.base
+    }
+}
+
+impl<K, V, S> AsRef<std::collections::HashMap<K, V, S>> for HashMap<K, V, S> {
+    fn as_ref(&self) -> &std::collections::HashMap<K, V, S> {
+        &self.base
+    }
+}
+
+impl<K, V, S> AsMut<std::collections::HashMap<K, V, S>> for HashMap<K, V, S> {
+    fn as_mut(&mut self) -> &mut std::collections::HashMap<K, V, S> {
+        &mut self.base
+    }
+}
+
+impl<K, V, S> From<std::collections::HashMap<K, V, S>> for HashMap<K, V, S> {
+    fn from(map: std::collections::HashMap<K, V, S>) -> HashMap<K, V, S> {
         HashMap { base: map }
     }
 }
 
+impl<K, V, S> From<HashMap<K, V, S>> for std::collections::HashMap<K, V, S> {
+    fn from(map: HashMap<K, V, S>) -> std::collections::HashMap<K, V, S> {
+        map.base
+    }
+}
+
+/// Collects the pairs into a map. Later pairs overwrite earlier ones with
+/// the same key.
+impl<K, V> From<Vec<(K, V)>> for HashMap<K, V>
+where
+    K: Eq + Hash,
+{
+    fn from(vec: Vec<(K, V)>) -> HashMap<K, V> {
+        HashMap {
+            base: vec.base.into_iter().collect(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::borrow::{Borrow, BorrowMut};
 
+    use gdnative::{
+        export::Export,
+        prelude::{ToVariant, Variant},
+    };
+
     use crate::collections::HashMap;
 
+    #[test]
+    fn test_export_bounds() {
+        // `ExportInfo` cannot be inspected, so this only checks that values
+        // need `ToVariant` and not `Export`, as with gdnative's own maps.
+        struct Stats;
+
+        impl ToVariant for Stats {
+            fn to_variant(&self) -> Variant {
+                Variant::nil()
+            }
+        }
+
+        fn assert_export<T: Export>() {}
+        assert_export::<HashMap<i64, Stats>>();
+    }
+
     #[test]
     fn test_borrow() {
         let map: HashMap<i32, i32> = HashMap::from([(2, 4), (1, 2), (3, 6)]);
@@ -285,4 +355,26 @@ mod tests {
         let std: &std::collections::HashMap<i32, i32> = map.borrow_mut();
         assert_eq!(std.len(), 3);
     }
+
+    #[test]
+    fn test_into_inner() {
+        let map: HashMap<i32, i32> = HashMap::from([(2, 4), (1, 2)]);
+        let std: std::collections::HashMap<i32, i32> = map.clone().into_inner();
+        assert_eq!(std, map);
+        assert_eq!(std::collections::HashMap::from(map), std);
+    }
+
+    #[test]
+    fn test_as_ref() {
+        let mut map: HashMap<i32, i32> = HashMap::from([(2, 4)]);
+        AsMut::<std::collections::HashMap<i32, i32>>::as_mut(&mut map).insert(1, 2);
+        let std: &std::collections::HashMap<i32, i32> = map.as_ref();
+        assert_eq!(std.len(), 2);
+    }
+
+    #[test]
+    fn test_from_vec() {
+        let map = HashMap::from(crate::vec::Vec::from(vec![(1, "a"), (2, "b"), (1, "c")]));
+        assert_eq!(map, HashMap::from([(1, "c"), (2, "b")]));
+    }
 }
@@ -11,6 +11,9 @@ use std::{
     ops::FnMut,
 };
 
+#[cfg(feature = "nightly")]
+use std::collections::hash_map::OccupiedError;
+
 use crate::collections::HashMap;
 
 impl<K, V> HashMap<K, V, RandomState> {
@@ -644,6 +647,65 @@ where
         self.base.get_mut(k)
     }
 
+    /// Attempts to get mutable references to `N` values in the map at once.
+    ///
+    /// Returns an array of length `N` with the results of each query. For
+    /// soundness, at most one mutable reference will be returned to any
+    /// value. `None` will be used if the key is missing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any keys are overlapping.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gdvariants::collections::HashMap;
+    ///
+    /// let mut libraries = HashMap::new();
+    /// libraries.insert("Bodleian Library".to_string(), 1602);
+    /// libraries.insert("Athenæum".to_string(), 1807);
+    ///
+    /// let [Some(a), None] = libraries.get_disjoint_mut(["Athenæum", "Library of Congress"]) else {
+    ///     panic!()
+    /// };
+    /// *a += 1;
+    /// assert_eq!(libraries["Athenæum"], 1808);
+    /// ```
+    #[cfg(feature = "rust_1_86")]
+    #[clippy::msrv = "1.86"]
+    #[inline]
+    pub fn get_disjoint_mut<Q, const N: usize>(&mut self, ks: [&Q; N]) -> [Option<&'_ mut V>; N]
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.base.get_disjoint_mut(ks)
+    }
+
+    /// Attempts to get mutable references to `N` values in the map at once,
+    /// without validating that the values are unique.
+    ///
+    /// # Safety
+    ///
+    /// Calling this method with overlapping keys is *[undefined behavior]*
+    /// even if the resulting references are not used.
+    ///
+    /// [undefined behavior]: https://doc.rust-lang.org/reference/behavior-considered-undefined.html
+    #[cfg(feature = "rust_1_86")]
+    #[clippy::msrv = "1.86"]
+    #[inline]
+    pub unsafe fn get_disjoint_unchecked_mut<Q, const N: usize>(
+        &mut self,
+        ks: [&Q; N],
+    ) -> [Option<&'_ mut V>; N]
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.base.get_disjoint_unchecked_mut(ks)
+    }
+
     /// Inserts a key-value pair into the map.
     ///
     /// If the map did not have this key present, [`None`] is returned.
@@ -673,6 +735,33 @@ where
         self.base.insert(k, v)
     }
 
+    /// Tries to insert a key-value pair into the map, and returns a mutable
+    /// reference to the value in the entry.
+    ///
+    /// If the map already had this key present, nothing is updated, and an
+    /// error containing the occupied entry and the value is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(map_try_insert)]
+    ///
+    /// use gdvariants::collections::HashMap;
+    ///
+    /// let mut map = HashMap::new();
+    /// assert_eq!(map.try_insert(37, "a").unwrap(), &"a");
+    ///
+    /// let err = map.try_insert(37, "b").unwrap_err();
+    /// assert_eq!(err.entry.key(), &37);
+    /// assert_eq!(err.entry.get(), &"a");
+    /// assert_eq!(err.value, "b");
+    /// ```
+    #[cfg(feature = "nightly")]
+    #[inline]
+    pub fn try_insert(&mut self, key: K, value: V) -> Result<&mut V, OccupiedError<'_, K, V>> {
+        self.base.try_insert(key, value)
+    }
+
     /// Removes a key from the map, returning the value at the key if the key
     /// was previously in the map.
     ///
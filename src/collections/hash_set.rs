@@ -6,10 +6,12 @@ use std::{
 
 use gdnative::{
     core_types::VariantArray,
-    export::{Export, ExportInfo},
+    export::{hint::ArrayHint, Export, ExportInfo},
     prelude::{FromVariant, FromVariantError, OwnedToVariant, ToVariant, Variant},
 };
 
+use crate::{hint::CollectionHint, vec::Vec, with};
+
 /// A [hash set] implemented as a `HashMap` where the value is `()`.
 ///
 /// As with the [`HashMap`] type, a `HashSet` requires that the elements
@@ -100,16 +102,20 @@ pub struct HashSet<T, S = RandomState> {
     pub(crate) base: std::collections::HashSet<T, S>,
 }
 
-pub enum NoHint {}
-
 impl<T> Export for HashSet<T>
 where
-    T: ToVariant,
+    T: Export,
 {
-    type Hint = NoHint;
+    type Hint = CollectionHint<T::Hint>;
 
-    fn export_info(_hint: Option<Self::Hint>) -> ExportInfo {
-        ExportInfo::new(gdnative::core_types::VariantType::VariantArray)
+    /// Exports a typed array, so the inspector uses the editor of the element
+    /// type for each element. The element hint defaults to that of `T`.
+    fn export_info(hint: Option<Self::Hint>) -> ExportInfo {
+        match hint.and_then(CollectionHint::into_element) {
+            Some(element) => ArrayHint::with_element_hint::<T>(element),
+            None => ArrayHint::with_element::<T>(),
+        }
+        .export_info()
     }
 }
 
@@ -119,14 +125,9 @@ where
     S: BuildHasher + Default,
 {
     fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
-        let array = VariantArray::from_variant(variant)?;
-        let mut hash_set: HashSet<T, S> =
-            HashSet::with_capacity_and_hasher(array.len() as usize, S::default());
-        for variant_value in array.iter() {
-            let value = T::from_variant(&variant_value)?;
-            hash_set.insert(value);
-        }
-        Ok(hash_set)
+        Ok(HashSet {
+            base: with::hash_set::from_variant(variant)?,
+        })
     }
 }
 
@@ -135,9 +136,20 @@ where
     T: ToVariant,
 {
     fn to_variant(&self) -> Variant {
+        with::hash_set::to_variant(&self.base)
+    }
+}
+
+impl<T, S> HashSet<T, S>
+where
+    T: OwnedToVariant,
+{
+    /// Converts the set into a `Variant` holding an `Array`, moving each
+    /// element into it instead of borrowing.
+    pub fn into_variant(self) -> Variant {
         let array = VariantArray::new();
-        for value in &self.base {
-            array.push(value.to_variant());
+        for value in self.base {
+            array.push(value);
         }
         array.owned_to_variant()
     }
@@ -155,12 +167,50 @@ impl<T, S> BorrowMut<std::collections::HashSet<T, S>> for HashSet<T, S> {
     }
 }
 
-impl<T> From<std::collections::HashSet<T>> for HashSet<T> {
-    fn from(set: std::collections::HashSet<T>) -> HashSet<T> {
+impl<T, S> HashSet<T, S> {
+    /// Returns the std set this set wraps, without copying.
+    #[inline]
+    pub fn into_inner(self) -> std::collections::HashSet<T, S> {
+        self.base
+    }
+}
+
+impl<T, S> AsRef<std::collections::HashSet<T, S>> for HashSet<T, S> {
+    fn as_ref(&self) -> &std::collections::HashSet<T, S> {
+        &self.base
+    }
+}
+
+impl<T, S> AsMut<std::collections::HashSet<T, S>> for HashSet<T, S> {
+    fn as_mut(&mut self) -> &mut std::collections::HashSet<T, S> {
+        &mut self.base
+    }
+}
+
+impl<T, S> From<std::collections::HashSet<T, S>> for HashSet<T, S> {
+    fn from(set: std::collections::HashSet<T, S>) -> HashSet<T, S> {
         HashSet { base: set }
     }
 }
 
+impl<T, S> From<HashSet<T, S>> for std::collections::HashSet<T, S> {
+    fn from(set: HashSet<T, S>) -> std::collections::HashSet<T, S> {
+        set.base
+    }
+}
+
+/// Collects the elements into a set, dropping duplicates.
+impl<T> From<Vec<T>> for HashSet<T>
+where
+    T: Eq + Hash,
+{
+    fn from(vec: Vec<T>) -> HashSet<T> {
+        HashSet {
+            base: vec.base.into_iter().collect(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::borrow::{Borrow, BorrowMut};
@@ -180,4 +230,26 @@ mod tests {
         let std: &std::collections::HashSet<i32> = set.borrow_mut();
         assert_eq!(std.len(), 3);
     }
+
+    #[test]
+    fn test_into_inner() {
+        let set: HashSet<i32> = HashSet::from([2, 1, 3]);
+        let std: std::collections::HashSet<i32> = set.clone().into_inner();
+        assert_eq!(std, set);
+        assert_eq!(std::collections::HashSet::from(set), std);
+    }
+
+    #[test]
+    fn test_as_ref() {
+        let mut set: HashSet<i32> = HashSet::from([2]);
+        AsMut::<std::collections::HashSet<i32>>::as_mut(&mut set).insert(1);
+        let std: &std::collections::HashSet<i32> = set.as_ref();
+        assert_eq!(std.len(), 2);
+    }
+
+    #[test]
+    fn test_from_vec() {
+        let set = HashSet::from(crate::vec::Vec::from(vec![2, 1, 2]));
+        assert_eq!(set, HashSet::from([1, 2]));
+    }
 }
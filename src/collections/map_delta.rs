@@ -0,0 +1,280 @@
+use std::hash::{BuildHasher, Hash};
+
+use gdnative::{
+    core_types::VariantArray,
+    prelude::{FromVariant, FromVariantError, OwnedToVariant, ToVariant, ToVariantEq, Variant},
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{collections::HashMap, vec::Vec};
+
+/// The changes between two states of a [`HashMap`].
+///
+/// Created with [`HashMap::diff`] and replayed with [`HashMap::apply`], so
+/// that only the entries that changed have to be sent over the network.
+///
+/// Converts to a `Variant` holding the `Array` `[inserted, changed, removed]`,
+/// where `inserted` and `changed` are `Dictionary`s and `removed` is an
+/// `Array` of keys. Serde uses the same three-element tuple.
+///
+/// # Examples
+///
+/// ```
+/// use gdvariants::collections::HashMap;
+///
+/// let old = HashMap::from([(1, 100), (2, 100)]);
+/// let new = HashMap::from([(1, 80), (3, 100)]);
+///
+/// let delta = new.diff(&old);
+/// assert_eq!(delta.inserted(), &HashMap::from([(3, 100)]));
+/// assert_eq!(delta.changed(), &HashMap::from([(1, 80)]));
+/// assert_eq!(delta.removed(), [2]);
+///
+/// let mut replica = old.clone();
+/// replica.apply(&delta);
+/// assert_eq!(replica, new);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MapDelta<K, V> {
+    pub(crate) inserted: HashMap<K, V>,
+    pub(crate) changed: HashMap<K, V>,
+    pub(crate) removed: Vec<K>,
+}
+
+impl<K, V> MapDelta<K, V> {
+    /// Creates a delta that changes nothing.
+    #[inline]
+    #[must_use]
+    pub fn new() -> MapDelta<K, V> {
+        MapDelta {
+            inserted: HashMap::new(),
+            changed: HashMap::new(),
+            removed: Vec::new(),
+        }
+    }
+
+    /// Returns the entries whose keys were not in the old map.
+    #[inline]
+    pub fn inserted(&self) -> &HashMap<K, V> {
+        &self.inserted
+    }
+
+    /// Returns the entries whose values differ from the old map.
+    #[inline]
+    pub fn changed(&self) -> &HashMap<K, V> {
+        &self.changed
+    }
+
+    /// Returns the keys that are missing from the new map.
+    #[inline]
+    pub fn removed(&self) -> &[K] {
+        &self.removed
+    }
+
+    /// Returns `true` if the delta changes nothing.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inserted.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl<K, V> Default for MapDelta<K, V> {
+    #[inline]
+    fn default() -> MapDelta<K, V> {
+        MapDelta::new()
+    }
+}
+
+impl<K, V> PartialEq for MapDelta<K, V>
+where
+    K: Eq + Hash,
+    V: PartialEq,
+{
+    fn eq(&self, other: &MapDelta<K, V>) -> bool {
+        self.inserted == other.inserted
+            && self.changed == other.changed
+            && self.removed == other.removed
+    }
+}
+
+impl<K, V> Eq for MapDelta<K, V>
+where
+    K: Eq + Hash,
+    V: Eq,
+{
+}
+
+impl<K, V, S> HashMap<K, V, S>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+    S: BuildHasher,
+{
+    /// Returns the changes that turn `old` into this map.
+    pub fn diff(&self, old: &HashMap<K, V, S>) -> MapDelta<K, V>
+    where
+        V: PartialEq,
+    {
+        let mut delta = MapDelta::new();
+        for (key, value) in &self.base {
+            match old.base.get(key) {
+                None => {
+                    delta.inserted.insert(key.clone(), value.clone());
+                }
+                Some(old_value) if old_value != value => {
+                    delta.changed.insert(key.clone(), value.clone());
+                }
+                Some(_) => {}
+            }
+        }
+        for key in old.base.keys() {
+            if !self.base.contains_key(key) {
+                delta.removed.push(key.clone());
+            }
+        }
+        delta
+    }
+
+    /// Applies the changes of a delta created with [`diff`](Self::diff).
+    ///
+    /// Applying `new.diff(&old)` to `old` makes it equal to `new`.
+    pub fn apply(&mut self, delta: &MapDelta<K, V>) {
+        for key in delta.removed.iter() {
+            self.base.remove(key);
+        }
+        for (key, value) in delta.inserted.iter().chain(delta.changed.iter()) {
+            self.base.insert(key.clone(), value.clone());
+        }
+    }
+}
+
+impl<K, V> FromVariant for MapDelta<K, V>
+where
+    K: Eq + Hash + FromVariant,
+    V: FromVariant,
+{
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        let array = VariantArray::from_variant(variant)?;
+        if array.len() != 3 {
+            return Err(FromVariantError::InvalidLength {
+                len: array.len() as usize,
+                expected: 3,
+            });
+        }
+        Ok(MapDelta {
+            inserted: HashMap::from_variant(&array.get(0))?,
+            changed: HashMap::from_variant(&array.get(1))?,
+            removed: Vec::from_variant(&array.get(2))?,
+        })
+    }
+}
+
+impl<K, V> ToVariant for MapDelta<K, V>
+where
+    K: ToVariantEq + ToVariant,
+    V: ToVariant,
+{
+    fn to_variant(&self) -> Variant {
+        let array = VariantArray::new();
+        array.push(self.inserted.to_variant());
+        array.push(self.changed.to_variant());
+        array.push(self.removed.to_variant());
+        array.owned_to_variant()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<K, V> Serialize for MapDelta<K, V>
+where
+    K: Eq + Hash + Serialize,
+    V: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (&self.inserted, &self.changed, &self.removed).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, K, V> Deserialize<'de> for MapDelta<K, V>
+where
+    K: Eq + Hash + Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (inserted, changed, removed) = Deserialize::deserialize(deserializer)?;
+        Ok(MapDelta {
+            inserted,
+            changed,
+            removed,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collections::{HashMap, MapDelta};
+
+    fn round_trip(old: &HashMap<i64, &'static str>, new: &HashMap<i64, &'static str>) {
+        let delta = new.diff(old);
+        let mut map = old.clone();
+        map.apply(&delta);
+
+        assert_eq!(&map, new);
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = HashMap::from([(1, "a"), (2, "b"), (3, "c")]);
+        let new = HashMap::from([(1, "a"), (2, "x"), (4, "d")]);
+
+        let delta = new.diff(&old);
+
+        assert_eq!(delta.inserted(), &HashMap::from([(4, "d")]));
+        assert_eq!(delta.changed(), &HashMap::from([(2, "x")]));
+        assert_eq!(delta.removed(), [3]);
+    }
+
+    #[test]
+    fn test_diff_unchanged() {
+        let map = HashMap::from([(1, "a"), (2, "b")]);
+
+        assert!(map.diff(&map).is_empty());
+        assert_eq!(map.diff(&map), MapDelta::new());
+    }
+
+    #[test]
+    fn test_apply_round_trip() {
+        let empty = HashMap::new();
+        let old = HashMap::from([(1, "a"), (2, "b"), (3, "c")]);
+        let new = HashMap::from([(1, "a"), (2, "x"), (4, "d")]);
+
+        round_trip(&old, &new);
+        round_trip(&new, &old);
+        round_trip(&empty, &new);
+        round_trip(&old, &empty);
+        round_trip(&old, &old);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let old = HashMap::from([(1, 10), (2, 20)]);
+        let new = HashMap::from([(2, 25), (3, 30)]);
+        let delta = new.diff(&old);
+
+        let json = serde_json::to_string(&delta).unwrap();
+        let decoded: MapDelta<i64, i64> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, delta);
+
+        let mut map = old.clone();
+        map.apply(&decoded);
+        assert_eq!(map, new);
+    }
+}
@@ -4,5 +4,16 @@ mod hash_map_impl;
 mod hash_set;
 mod hash_set_impl;
 
+mod enum_map;
+mod flag_set;
+mod map_delta;
+mod set_delta;
+mod shared_map;
+
+pub use enum_map::EnumMap;
+pub use flag_set::FlagSet;
 pub use hash_map::HashMap;
 pub use hash_set::HashSet;
+pub use map_delta::MapDelta;
+pub use set_delta::SetDelta;
+pub use shared_map::{SharedMap, SharedMapEntry};
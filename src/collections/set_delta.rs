@@ -0,0 +1,213 @@
+use std::hash::{BuildHasher, Hash};
+
+use gdnative::{
+    core_types::VariantArray,
+    prelude::{FromVariant, FromVariantError, OwnedToVariant, ToVariant, Variant},
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::collections::HashSet;
+
+/// The changes between two states of a [`HashSet`].
+///
+/// Created with [`HashSet::diff`] and replayed with [`HashSet::apply`].
+///
+/// Converts to a `Variant` holding the `Array` `[inserted, removed]` of two
+/// `Array`s. Serde uses the same two-element tuple.
+///
+/// # Examples
+///
+/// ```
+/// use gdvariants::collections::HashSet;
+///
+/// let old = HashSet::from(["shield", "sword"]);
+/// let new = HashSet::from(["sword", "bow"]);
+///
+/// let delta = new.diff(&old);
+/// assert_eq!(delta.inserted(), &HashSet::from(["bow"]));
+/// assert_eq!(delta.removed(), &HashSet::from(["shield"]));
+///
+/// let mut replica = old.clone();
+/// replica.apply(&delta);
+/// assert_eq!(replica, new);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SetDelta<T> {
+    pub(crate) inserted: HashSet<T>,
+    pub(crate) removed: HashSet<T>,
+}
+
+impl<T> SetDelta<T> {
+    /// Creates a delta that changes nothing.
+    #[inline]
+    #[must_use]
+    pub fn new() -> SetDelta<T> {
+        SetDelta {
+            inserted: HashSet::new(),
+            removed: HashSet::new(),
+        }
+    }
+
+    /// Returns the values that were not in the old set.
+    #[inline]
+    pub fn inserted(&self) -> &HashSet<T> {
+        &self.inserted
+    }
+
+    /// Returns the values that are missing from the new set.
+    #[inline]
+    pub fn removed(&self) -> &HashSet<T> {
+        &self.removed
+    }
+
+    /// Returns `true` if the delta changes nothing.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inserted.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl<T> Default for SetDelta<T> {
+    #[inline]
+    fn default() -> SetDelta<T> {
+        SetDelta::new()
+    }
+}
+
+impl<T> PartialEq for SetDelta<T>
+where
+    T: Eq + Hash,
+{
+    fn eq(&self, other: &SetDelta<T>) -> bool {
+        self.inserted == other.inserted && self.removed == other.removed
+    }
+}
+
+impl<T> Eq for SetDelta<T> where T: Eq + Hash {}
+
+impl<T, S> HashSet<T, S>
+where
+    T: Eq + Hash + Clone,
+    S: BuildHasher,
+{
+    /// Returns the changes that turn `old` into this set.
+    pub fn diff(&self, old: &HashSet<T, S>) -> SetDelta<T> {
+        SetDelta {
+            inserted: self.base.difference(&old.base).cloned().collect(),
+            removed: old.base.difference(&self.base).cloned().collect(),
+        }
+    }
+
+    /// Applies the changes of a delta created with [`diff`](Self::diff).
+    ///
+    /// Applying `new.diff(&old)` to `old` makes it equal to `new`.
+    pub fn apply(&mut self, delta: &SetDelta<T>) {
+        for value in delta.removed.iter() {
+            self.base.remove(value);
+        }
+        self.base.extend(delta.inserted.iter().cloned());
+    }
+}
+
+impl<T> FromVariant for SetDelta<T>
+where
+    T: Eq + Hash + FromVariant,
+{
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        let array = VariantArray::from_variant(variant)?;
+        if array.len() != 2 {
+            return Err(FromVariantError::InvalidLength {
+                len: array.len() as usize,
+                expected: 2,
+            });
+        }
+        Ok(SetDelta {
+            inserted: HashSet::from_variant(&array.get(0))?,
+            removed: HashSet::from_variant(&array.get(1))?,
+        })
+    }
+}
+
+impl<T> ToVariant for SetDelta<T>
+where
+    T: ToVariant,
+{
+    fn to_variant(&self) -> Variant {
+        let array = VariantArray::new();
+        array.push(self.inserted.to_variant());
+        array.push(self.removed.to_variant());
+        array.owned_to_variant()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> Serialize for SetDelta<T>
+where
+    T: Eq + Hash + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (&self.inserted, &self.removed).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> Deserialize<'de> for SetDelta<T>
+where
+    T: Eq + Hash + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (inserted, removed) = Deserialize::deserialize(deserializer)?;
+        Ok(SetDelta { inserted, removed })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collections::{HashSet, SetDelta};
+
+    #[test]
+    fn test_diff() {
+        let old = HashSet::from([1, 2, 3]);
+        let new = HashSet::from([2, 3, 4]);
+
+        let delta = new.diff(&old);
+
+        assert_eq!(delta.inserted(), &HashSet::from([4]));
+        assert_eq!(delta.removed(), &HashSet::from([1]));
+    }
+
+    #[test]
+    fn test_apply_round_trip() {
+        let sets = [
+            HashSet::new(),
+            HashSet::from([1, 2, 3]),
+            HashSet::from([2, 3, 4]),
+        ];
+        for old in sets.iter() {
+            for new in sets.iter() {
+                let mut set = old.clone();
+                set.apply(&new.diff(old));
+                assert_eq!(&set, new);
+            }
+        }
+        assert_eq!(sets[1].diff(&sets[1]), SetDelta::new());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let delta = HashSet::from([2, 3]).diff(&HashSet::from([1, 2]));
+
+        let json = serde_json::to_string(&delta).unwrap();
+        let decoded: SetDelta<i64> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(decoded, delta);
+    }
+}
@@ -0,0 +1,353 @@
+use std::marker::PhantomData;
+
+use gdnative::{
+    core_types::{Dictionary, VariantType},
+    export::{Export, ExportInfo},
+    object::ownership::Shared,
+    prelude::{FromVariant, FromVariantError, OwnedToVariant, ToVariant, ToVariantEq, Variant},
+};
+
+use crate::{collections::HashMap, vec::Vec};
+
+/// A typed view over a live, shared Godot [`Dictionary`].
+///
+/// Unlike [`HashMap`], which copies the contents of a `Dictionary` when it is
+/// converted, a `SharedMap` reads and writes the underlying `Dictionary`
+/// directly. Changes made by scripts are visible from Rust and vice versa.
+/// Keys and values are converted to and from [`Variant`] on every access.
+///
+/// Values that were put into the dictionary by scripts are not guaranteed to
+/// convert into `V`. The lookup methods that return `Option<V>` treat such
+/// values as missing; use [`try_get`](Self::try_get) to see the conversion
+/// error instead. [`insert`](Self::insert) and [`remove`](Self::remove)
+/// return the error, after changing the map.
+///
+/// A `SharedMap` is neither `Send` nor `Sync`, as Godot does not synchronize
+/// access to a shared `Dictionary`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use gdvariants::collections::SharedMap;
+///
+/// let config: SharedMap<String, i64> = SharedMap::new();
+/// config.insert("volume".to_string(), 80).unwrap();
+/// config.entry("difficulty".to_string()).or_insert(1);
+///
+/// assert_eq!(config.get(&"volume".to_string()), Some(80));
+/// ```
+pub struct SharedMap<K, V> {
+    dictionary: Dictionary<Shared>,
+    _marker: PhantomData<(K, V, *const ())>,
+}
+
+impl<K, V> SharedMap<K, V> {
+    /// Creates a `SharedMap` backed by a new, empty `Dictionary`.
+    #[inline]
+    #[must_use]
+    pub fn new() -> SharedMap<K, V> {
+        SharedMap {
+            dictionary: Dictionary::new().into_shared(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a `SharedMap` that reads and writes `dictionary`.
+    ///
+    /// # Safety
+    ///
+    /// The same rules apply as for mutating a `Dictionary<Shared>`: for as
+    /// long as the returned map is in use, the dictionary must not be accessed
+    /// from any other thread.
+    #[inline]
+    pub unsafe fn from_dictionary(dictionary: Dictionary<Shared>) -> SharedMap<K, V> {
+        SharedMap {
+            dictionary,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a `SharedMap` from a `Variant` holding a `Dictionary`.
+    ///
+    /// # Safety
+    ///
+    /// See [`from_dictionary`](Self::from_dictionary).
+    #[inline]
+    pub unsafe fn from_variant(variant: &Variant) -> Result<SharedMap<K, V>, FromVariantError> {
+        Ok(SharedMap::from_dictionary(Dictionary::from_variant(
+            variant,
+        )?))
+    }
+
+    /// Returns a new reference to the underlying `Dictionary`, for passing it
+    /// on to Godot.
+    #[inline]
+    pub fn dictionary(&self) -> Dictionary<Shared> {
+        self.dictionary.clone()
+    }
+
+    /// Returns the number of elements in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.dictionary.len() as usize
+    }
+
+    /// Returns `true` if the map contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.dictionary.is_empty()
+    }
+
+    /// Clears the map, removing all key-value pairs.
+    #[inline]
+    pub fn clear(&self) {
+        unsafe { self.dictionary.clear() }
+    }
+}
+
+impl<K, V> SharedMap<K, V>
+where
+    K: ToVariant + ToVariantEq,
+    V: FromVariant,
+{
+    /// Returns the value corresponding to the key.
+    ///
+    /// Returns `None` if the key is missing or if its value cannot be
+    /// converted into `V`.
+    #[inline]
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.try_get(key).ok().flatten()
+    }
+
+    /// Returns the value corresponding to the key, or the error raised while
+    /// converting it into `V`.
+    pub fn try_get(&self, key: &K) -> Result<Option<V>, FromVariantError> {
+        match self.dictionary.get(key) {
+            Some(variant) => V::from_variant(&variant).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    #[inline]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.dictionary.contains(key)
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key
+    /// was previously in the map.
+    ///
+    /// The key is removed even if its value cannot be converted into `V`, in
+    /// which case the conversion error is returned.
+    pub fn remove(&self, key: &K) -> Result<Option<V>, FromVariantError> {
+        let previous = self.try_get(key);
+        unsafe { self.dictionary.erase(key) };
+        previous
+    }
+
+    /// Returns all values of the map, in the order Godot stores them.
+    pub fn values(&self) -> Result<Vec<V>, FromVariantError> {
+        self.dictionary
+            .values()
+            .iter()
+            .map(|variant| V::from_variant(&variant))
+            .collect()
+    }
+}
+
+impl<K, V> SharedMap<K, V>
+where
+    K: ToVariant + ToVariantEq + FromVariant,
+{
+    /// Returns all keys of the map, in the order Godot stores them.
+    pub fn keys(&self) -> Result<Vec<K>, FromVariantError> {
+        self.dictionary
+            .keys()
+            .iter()
+            .map(|variant| K::from_variant(&variant))
+            .collect()
+    }
+
+    /// Copies the current contents of the map into a [`HashMap`].
+    pub fn to_hash_map(&self) -> Result<HashMap<K, V>, FromVariantError>
+    where
+        K: std::hash::Hash,
+        V: FromVariant,
+    {
+        HashMap::from_variant(&self.dictionary.to_variant())
+    }
+}
+
+impl<K, V> SharedMap<K, V>
+where
+    K: ToVariant + ToVariantEq,
+    V: ToVariant + FromVariant,
+{
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map did have this key present, the value is updated, and the
+    /// old value is returned. The value is updated even if the old value
+    /// cannot be converted into `V`, in which case the conversion error is
+    /// returned.
+    pub fn insert(&self, key: K, value: V) -> Result<Option<V>, FromVariantError> {
+        let previous = self.try_get(&key);
+        unsafe { self.dictionary.insert(&key, &value) };
+        previous
+    }
+
+    /// Gets the given key's corresponding entry in the map for in-place
+    /// manipulation.
+    #[inline]
+    pub fn entry(&self, key: K) -> SharedMapEntry<'_, K, V> {
+        SharedMapEntry { map: self, key }
+    }
+}
+
+impl<K, V> Clone for SharedMap<K, V> {
+    /// Returns a `SharedMap` that refers to the same `Dictionary`.
+    fn clone(&self) -> SharedMap<K, V> {
+        SharedMap {
+            dictionary: self.dictionary.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<K, V> Default for SharedMap<K, V> {
+    #[inline]
+    fn default() -> SharedMap<K, V> {
+        SharedMap::new()
+    }
+}
+
+pub enum NoHint {}
+
+impl<K, V> Export for SharedMap<K, V> {
+    type Hint = NoHint;
+
+    fn export_info(_hint: Option<Self::Hint>) -> ExportInfo {
+        ExportInfo::new(VariantType::Dictionary)
+    }
+}
+
+impl<K, V> ToVariant for SharedMap<K, V> {
+    fn to_variant(&self) -> Variant {
+        self.dictionary.clone().owned_to_variant()
+    }
+}
+
+/// A view into a single key of a [`SharedMap`].
+///
+/// This is constructed from the [`entry`](SharedMap::entry) method on
+/// [`SharedMap`]. Since values live in the `Dictionary`, the methods return
+/// owned values instead of references.
+pub struct SharedMapEntry<'a, K, V> {
+    map: &'a SharedMap<K, V>,
+    key: K,
+}
+
+impl<'a, K, V> SharedMapEntry<'a, K, V>
+where
+    K: ToVariant + ToVariantEq,
+    V: ToVariant + FromVariant,
+{
+    /// Returns this entry's key.
+    #[inline]
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Ensures a value is in the entry by inserting the default if empty, and
+    /// returns the value in the entry.
+    #[inline]
+    pub fn or_insert(self, default: V) -> V {
+        self.or_insert_with(|| default)
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default
+    /// function if empty, and returns the value in the entry.
+    ///
+    /// A value that cannot be converted into `V` is replaced.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> V {
+        match self.map.get(&self.key) {
+            Some(value) => value,
+            None => {
+                let value = default();
+                unsafe { self.map.dictionary.insert(&self.key, &value) };
+                value
+            }
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the default value if
+    /// empty, and returns the value in the entry.
+    #[inline]
+    pub fn or_default(self) -> V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any
+    /// potential inserts into the map. The modified value is written back to
+    /// the `Dictionary`.
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        if let Some(mut value) = self.map.get(&self.key) {
+            f(&mut value);
+            unsafe { self.map.dictionary.insert(&self.key, &value) };
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gdnative::prelude::{Dictionary, ToVariant};
+
+    use crate::collections::SharedMap;
+
+    #[test]
+    #[ignore = "creating a Dictionary needs the Godot engine"]
+    fn test_insert_remove() {
+        let map: SharedMap<i64, i64> = SharedMap::new();
+
+        assert_eq!(map.insert(1, 2), Ok(None));
+        assert_eq!(map.insert(1, 3), Ok(Some(2)));
+        assert_eq!(map.get(&1), Some(3));
+        assert_eq!(map.remove(&1), Ok(Some(3)));
+        assert_eq!(map.remove(&1), Ok(None));
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    #[ignore = "creating a Dictionary needs the Godot engine"]
+    fn test_unconvertible_value() {
+        let dictionary = Dictionary::new();
+        dictionary.insert(1, "one");
+        let map: SharedMap<i64, i64> =
+            unsafe { SharedMap::from_dictionary(dictionary.into_shared()) };
+
+        assert_eq!(map.get(&1), None);
+        assert!(map.try_get(&1).is_err());
+        assert!(map.insert(1, 2).is_err());
+        assert_eq!(map.get(&1), Some(2));
+
+        unsafe { map.dictionary().insert(1, "one") };
+        assert!(map.remove(&1).is_err());
+        assert!(!map.contains_key(&1));
+    }
+
+    #[test]
+    #[ignore = "creating a Dictionary needs the Godot engine"]
+    fn test_shared() {
+        let map: SharedMap<i64, i64> = SharedMap::new();
+        let other = map.clone();
+
+        other.insert(1, 2).unwrap();
+        map.entry(1).and_modify(|value| *value += 1);
+
+        assert_eq!(other.get(&1), Some(3));
+        assert_eq!(map.dictionary().to_variant(), other.to_variant());
+    }
+}
@@ -0,0 +1,499 @@
+//! Godot's `ConfigFile` format, the INI-like files used for settings and
+//! `project.godot`.
+//!
+//! ```
+//! use gdvariants::{collections::HashMap, config_file};
+//!
+//! let text = "[display]\n\nwidth=1280\nheight=720\n";
+//! let sections: HashMap<String, HashMap<String, i64>> = config_file::parse(text).unwrap();
+//! assert_eq!(sections["display"]["width"], 1280);
+//!
+//! assert_eq!(config_file::write(&sections), "[display]\n\nheight=720\nwidth=1280\n");
+//! ```
+//!
+//! Values are written the way `var2str` writes them, see [`text`](crate::text).
+//! To change a file without losing its comments and the order of its keys,
+//! edit a [`ConfigFile`] instead.
+
+use std::fmt;
+
+use crate::{
+    collections::HashMap,
+    text::{write_value, ParseError, Parser},
+    value::{FromValue, FromValueError, ToValue, Value},
+};
+
+/// Parses the sections of a `ConfigFile` into maps of `V`.
+pub fn parse<V>(text: &str) -> Result<HashMap<String, HashMap<String, V>>, ParseError>
+where
+    V: FromValue,
+{
+    ConfigFile::parse(text)?.to_map().map_err(ParseError::Value)
+}
+
+/// Writes `sections` the way `ConfigFile.save` does, with sections and keys
+/// sorted by name.
+pub fn write<V>(sections: &HashMap<String, HashMap<String, V>>) -> String
+where
+    V: ToValue,
+{
+    let mut config = ConfigFile::new();
+    config.update(sections);
+    config.to_string()
+}
+
+/// The contents of a `ConfigFile`, in the order they were read or added.
+///
+/// Comments on their own lines are kept with the section or key that follows
+/// them, along with a blank line after them. Other blank lines follow the
+/// layout of `ConfigFile.save`, and comments at the end of a value's line are
+/// dropped.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConfigFile {
+    sections: std::vec::Vec<Section>,
+    comments: std::vec::Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Section {
+    comments: std::vec::Vec<String>,
+    name: String,
+    entries: std::vec::Vec<Entry>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Entry {
+    comments: std::vec::Vec<String>,
+    key: String,
+    value: Value,
+}
+
+impl ConfigFile {
+    /// Creates an empty `ConfigFile`.
+    #[inline]
+    pub fn new() -> ConfigFile {
+        ConfigFile::default()
+    }
+
+    /// Parses the text of a `ConfigFile`.
+    ///
+    /// Keys before the first section header belong to the section `""`.
+    pub fn parse(text: &str) -> Result<ConfigFile, ParseError> {
+        let mut config = ConfigFile::new();
+        let mut comments: std::vec::Vec<String> = std::vec::Vec::new();
+        let mut section = String::new();
+        let mut pos = 0;
+        while pos < text.len() {
+            let line_end = text[pos..]
+                .find('\n')
+                .map_or(text.len(), |index| pos + index);
+            let raw = &text[pos..line_end];
+            let line = raw.trim();
+            let start = pos + raw.len() - raw.trim_start().len();
+            if line.is_empty() {
+                if comments.last().map_or(false, |comment| !comment.is_empty()) {
+                    comments.push(String::new());
+                }
+                pos = line_end + 1;
+            } else if line.starts_with(';') {
+                comments.push(line.to_string());
+                pos = line_end + 1;
+            } else if let Some(header) = line.strip_prefix('[') {
+                let name = header.strip_suffix(']').ok_or_else(|| {
+                    Parser::at(text, start).error("expected ']' at the end of the section header")
+                })?;
+                section = name.to_string();
+                let index = config.section_index(name);
+                config.sections[index].comments.append(&mut comments);
+                pos = line_end + 1;
+            } else {
+                let mut parser = Parser::at(text, start);
+                let key = if line.starts_with('"') {
+                    parser.string()?
+                } else {
+                    let equals = line
+                        .find('=')
+                        .ok_or_else(|| parser.error("expected '=' after the key"))?;
+                    let key = line[..equals].trim_end().to_string();
+                    parser = Parser::at(text, start + equals);
+                    key
+                };
+                parser.expect('=')?;
+                let value = parser.value()?;
+                pos = parser.position();
+                let rest = text[pos..].split('\n').next().unwrap_or("").trim();
+                if !rest.is_empty() && !rest.starts_with(';') {
+                    return Err(parser.error(format!("unexpected '{}' after the value", rest)));
+                }
+                pos += text[pos..]
+                    .find('\n')
+                    .map_or(text.len() - pos, |index| index + 1);
+                let index = config.section_index(&section);
+                let section = &mut config.sections[index];
+                match section.entries.iter_mut().find(|entry| entry.key == key) {
+                    Some(entry) => {
+                        entry.comments.append(&mut comments);
+                        entry.value = value;
+                    }
+                    None => section.entries.push(Entry {
+                        comments: std::mem::take(&mut comments),
+                        key,
+                        value,
+                    }),
+                }
+            }
+        }
+        while comments.last().map_or(false, String::is_empty) {
+            comments.pop();
+        }
+        config.comments = comments;
+        Ok(config)
+    }
+
+    /// Returns the index of the section `name`, adding it if needed.
+    ///
+    /// The section `""` has no header, so it is always kept first.
+    fn section_index(&mut self, name: &str) -> usize {
+        if let Some(index) = self
+            .sections
+            .iter()
+            .position(|section| section.name == name)
+        {
+            return index;
+        }
+        let section = Section {
+            comments: std::vec::Vec::new(),
+            name: name.to_string(),
+            entries: std::vec::Vec::new(),
+        };
+        if name.is_empty() {
+            self.sections.insert(0, section);
+            0
+        } else {
+            self.sections.push(section);
+            self.sections.len() - 1
+        }
+    }
+
+    fn section(&self, name: &str) -> Option<&Section> {
+        self.sections.iter().find(|section| section.name == name)
+    }
+
+    /// Returns `true` if the file has a section named `section`.
+    #[inline]
+    pub fn has_section(&self, section: &str) -> bool {
+        self.section(section).is_some()
+    }
+
+    /// Returns the names of the sections, in order.
+    pub fn sections(&self) -> impl Iterator<Item = &str> {
+        self.sections.iter().map(|section| section.name.as_str())
+    }
+
+    /// Returns the keys of `section` in order, or nothing if it does not exist.
+    pub fn keys(&self, section: &str) -> impl Iterator<Item = &str> {
+        self.section(section)
+            .into_iter()
+            .flat_map(|section| section.entries.iter().map(|entry| entry.key.as_str()))
+    }
+
+    /// Returns the value of `key` in `section`.
+    pub fn get_value(&self, section: &str, key: &str) -> Option<&Value> {
+        self.section(section)?
+            .entries
+            .iter()
+            .find(|entry| entry.key == key)
+            .map(|entry| &entry.value)
+    }
+
+    /// Sets the value of `key` in `section`, keeping its place if it already
+    /// exists and adding it at the end of the section otherwise.
+    pub fn set_value<T>(&mut self, section: &str, key: &str, value: &T)
+    where
+        T: ToValue + ?Sized,
+    {
+        let index = self.section_index(section);
+        let entries = &mut self.sections[index].entries;
+        let value = value.to_value();
+        match entries.iter_mut().find(|entry| entry.key == key) {
+            Some(entry) => entry.value = value,
+            None => entries.push(Entry {
+                comments: std::vec::Vec::new(),
+                key: key.to_string(),
+                value,
+            }),
+        }
+    }
+
+    /// Removes `key` from `section`, returning its value.
+    pub fn erase_section_key(&mut self, section: &str, key: &str) -> Option<Value> {
+        let section = self
+            .sections
+            .iter_mut()
+            .find(|current| current.name == section)?;
+        let index = section.entries.iter().position(|entry| entry.key == key)?;
+        Some(section.entries.remove(index).value)
+    }
+
+    /// Removes `section` and all of its keys.
+    pub fn erase_section(&mut self, section: &str) {
+        self.sections.retain(|current| current.name != section);
+    }
+
+    /// Converts the sections into maps of `V`.
+    pub fn to_map<V>(&self) -> Result<HashMap<String, HashMap<String, V>>, FromValueError>
+    where
+        V: FromValue,
+    {
+        FromValue::from_value(&self.to_value())
+    }
+
+    /// Replaces the contents with `sections`.
+    ///
+    /// Sections and keys that already exist keep their place and comments.
+    /// New ones are added at the end, sorted by name.
+    pub fn update<V>(&mut self, sections: &HashMap<String, HashMap<String, V>>)
+    where
+        V: ToValue,
+    {
+        self.sections
+            .retain(|section| sections.contains_key(&section.name));
+        for section in &mut self.sections {
+            let values = &sections[&section.name];
+            section
+                .entries
+                .retain(|entry| values.contains_key(&entry.key));
+            for entry in &mut section.entries {
+                entry.value = values[&entry.key].to_value();
+            }
+        }
+        let mut names: std::vec::Vec<&String> = sections.keys().collect();
+        names.sort();
+        for name in names {
+            self.section_index(name);
+            let mut keys: std::vec::Vec<(&String, &V)> = sections[name].iter().collect();
+            keys.sort_by(|(a, _), (b, _)| a.cmp(b));
+            for (key, value) in keys {
+                self.set_value(name, key, value);
+            }
+        }
+    }
+}
+
+impl ToValue for ConfigFile {
+    /// Converts the file into a `Dictionary` of sections, each a `Dictionary`
+    /// of keys.
+    fn to_value(&self) -> Value {
+        Value::Dictionary(
+            self.sections
+                .iter()
+                .map(|section| {
+                    let entries = section
+                        .entries
+                        .iter()
+                        .map(|entry| (Value::String(entry.key.clone()), entry.value.clone()))
+                        .collect();
+                    (
+                        Value::String(section.name.clone()),
+                        Value::Dictionary(entries),
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Writes keys like Godot's `property_name_encode`, which quotes them if they
+/// contain `=`, `"`, whitespace or non-ASCII characters.
+fn write_key(f: &mut fmt::Formatter<'_>, key: &str) -> fmt::Result {
+    if key
+        .chars()
+        .any(|c| c == '=' || c == '"' || !('!'..='~').contains(&c))
+    {
+        f.write_str(&write_value(&Value::String(key.to_string())))
+    } else {
+        f.write_str(key)
+    }
+}
+
+fn write_comments(f: &mut fmt::Formatter<'_>, comments: &[String]) -> fmt::Result {
+    for comment in comments {
+        writeln!(f, "{}", comment)?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for ConfigFile {
+    /// Writes the file the way `ConfigFile.save` does.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, section) in self.sections.iter().enumerate() {
+            if index > 0 {
+                f.write_str("\n")?;
+            }
+            write_comments(f, &section.comments)?;
+            if !section.name.is_empty() {
+                write!(f, "[{}]\n\n", section.name)?;
+            }
+            for entry in &section.entries {
+                write_comments(f, &entry.comments)?;
+                write_key(f, &entry.key)?;
+                writeln!(f, "={}", write_value(&entry.value))?;
+            }
+        }
+        if !self.comments.is_empty() {
+            if !self.sections.is_empty() {
+                f.write_str("\n")?;
+            }
+            write_comments(f, &self.comments)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gdnative::core_types::Vector2;
+
+    use crate::{
+        collections::HashMap,
+        config_file::{parse, write, ConfigFile},
+        text::ParseError,
+        value::Value,
+    };
+
+    const SETTINGS: &str = "\
+; Player settings
+
+[display]
+
+fullscreen=true
+; Logical size.
+size=Vector2( 1280, 720 )
+
+[input]
+
+\"move left\"=[ \"a\", \"left\" ]
+";
+
+    #[test]
+    fn test_parse() {
+        let config = ConfigFile::parse(SETTINGS).unwrap();
+
+        assert_eq!(config.sections().collect::<Vec<_>>(), ["display", "input"]);
+        assert_eq!(
+            config.keys("display").collect::<Vec<_>>(),
+            ["fullscreen", "size"]
+        );
+        assert_eq!(
+            config.get_value("display", "size"),
+            Some(&Value::Vector2(Vector2::new(1280.0, 720.0)))
+        );
+        assert_eq!(
+            config.get_value("input", "move left"),
+            Some(&Value::Array(vec![
+                Value::String("a".to_string()),
+                Value::String("left".to_string()),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_round_trip_keeps_comments() {
+        let config = ConfigFile::parse(SETTINGS).unwrap();
+
+        assert_eq!(config.to_string(), SETTINGS);
+    }
+
+    #[test]
+    fn test_set_value_keeps_order() {
+        let mut config = ConfigFile::parse(SETTINGS).unwrap();
+        config.set_value("display", "fullscreen", &false);
+        config.set_value("display", "vsync", &true);
+        config.set_value("", "config_version", &4);
+
+        assert_eq!(
+            config.to_string(),
+            "config_version=4\n\n; Player settings\n\n[display]\n\nfullscreen=false\n\
+             ; Logical size.\nsize=Vector2( 1280, 720 )\nvsync=true\n\n[input]\n\n\
+             \"move left\"=[ \"a\", \"left\" ]\n"
+        );
+    }
+
+    #[test]
+    fn test_update() {
+        let mut config = ConfigFile::parse(SETTINGS).unwrap();
+        let mut sections = config.to_map::<Value>().unwrap();
+        sections.remove("input");
+        sections
+            .get_mut("display")
+            .unwrap()
+            .insert("scale".to_string(), Value::Real(1.5));
+        config.update(&sections);
+
+        assert_eq!(
+            config.to_string(),
+            "; Player settings\n\n[display]\n\nfullscreen=true\n\
+             ; Logical size.\nsize=Vector2( 1280, 720 )\nscale=1.5\n"
+        );
+    }
+
+    #[test]
+    fn test_map() {
+        let sections = HashMap::from([
+            (
+                "b".to_string(),
+                HashMap::from([("y".to_string(), 2), ("x".to_string(), 1)]),
+            ),
+            ("a".to_string(), HashMap::from([("z".to_string(), 3)])),
+        ]);
+        let text = write(&sections);
+
+        assert_eq!(text, "[a]\n\nz=3\n\n[b]\n\nx=1\ny=2\n");
+        assert_eq!(parse(&text), Ok(sections));
+    }
+
+    #[test]
+    fn test_keys_without_section() {
+        let config = ConfigFile::parse("volume = 0.5\n[audio]\nmuted=false").unwrap();
+
+        assert_eq!(config.sections().collect::<Vec<_>>(), ["", "audio"]);
+        assert_eq!(config.get_value("", "volume"), Some(&Value::Real(0.5)));
+        assert_eq!(config.to_string(), "volume=0.5\n\n[audio]\n\nmuted=false\n");
+    }
+
+    #[test]
+    fn test_multiline_value() {
+        let config = ConfigFile::parse("[a]\n\nmap={\n\"b\": 1\n}\nc=2\n").unwrap();
+
+        assert_eq!(config.keys("a").collect::<Vec<_>>(), ["map", "c"]);
+        assert_eq!(config.to_string(), "[a]\n\nmap={\n\"b\": 1\n}\nc=2\n");
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |line, column, message: &str| {
+            Err(ParseError::Syntax {
+                line,
+                column,
+                message: message.to_string(),
+            })
+        };
+
+        assert_eq!(
+            ConfigFile::parse("[a\nb=1"),
+            error(1, 1, "expected ']' at the end of the section header")
+        );
+        assert_eq!(
+            ConfigFile::parse("[a]\n  b"),
+            error(2, 3, "expected '=' after the key")
+        );
+        assert_eq!(
+            ConfigFile::parse("b=1 2"),
+            error(1, 4, "unexpected '2' after the value")
+        );
+        assert!(matches!(
+            parse::<i32>("[a]\nb=\"c\""),
+            Err(ParseError::Value(_))
+        ));
+    }
+}
@@ -0,0 +1,116 @@
+//! Support for C-like Rust enums in exported properties.
+
+use std::hash::Hash;
+
+/// A C-like enum with a fixed, ordered list of named variants.
+///
+/// Used by [`FlagSet`](crate::collections::FlagSet) to map variants to bits
+/// and by [`EnumMap`](crate::collections::EnumMap) to map variants to slots.
+/// The position of a variant in [`VARIANTS`](GodotEnum::VARIANTS) determines
+/// its bit and slot, so new variants should be appended at the end to keep
+/// saved data valid.
+///
+/// # Examples
+///
+/// ```
+/// use gdvariants::enums::GodotEnum;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// enum Tag {
+///     Flying,
+///     Swimming,
+/// }
+///
+/// impl GodotEnum for Tag {
+///     const VARIANTS: &'static [Self] = &[Tag::Flying, Tag::Swimming];
+///
+///     fn name(self) -> &'static str {
+///         match self {
+///             Tag::Flying => "flying",
+///             Tag::Swimming => "swimming",
+///         }
+///     }
+/// }
+///
+/// assert_eq!(Tag::Swimming.index(), 1);
+/// assert_eq!(Tag::from_name("flying"), Some(Tag::Flying));
+/// ```
+pub trait GodotEnum: Copy + Eq + Hash + 'static {
+    /// All variants, in the order they are shown in the inspector.
+    const VARIANTS: &'static [Self];
+
+    /// Returns the name of the variant as shown in the inspector.
+    fn name(self) -> &'static str;
+
+    /// Returns the position of the variant in [`VARIANTS`](GodotEnum::VARIANTS).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the variant is missing from `VARIANTS`.
+    #[inline]
+    fn index(self) -> usize {
+        Self::VARIANTS
+            .iter()
+            .position(|variant| *variant == self)
+            .expect("variant is missing from GodotEnum::VARIANTS")
+    }
+
+    /// Returns the variant with the given name.
+    #[inline]
+    fn from_name(name: &str) -> Option<Self> {
+        Self::VARIANTS
+            .iter()
+            .copied()
+            .find(|variant| variant.name() == name)
+    }
+
+    /// Returns the names of all variants, in order.
+    #[inline]
+    fn names() -> std::vec::Vec<String> {
+        Self::VARIANTS
+            .iter()
+            .map(|variant| variant.name().to_string())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::enums::GodotEnum;
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    pub(crate) enum Element {
+        Fire,
+        Water,
+        Earth,
+    }
+
+    impl GodotEnum for Element {
+        const VARIANTS: &'static [Self] = &[Element::Fire, Element::Water, Element::Earth];
+
+        fn name(self) -> &'static str {
+            match self {
+                Element::Fire => "fire",
+                Element::Water => "water",
+                Element::Earth => "earth",
+            }
+        }
+    }
+
+    #[test]
+    fn test_index() {
+        assert_eq!(Element::Fire.index(), 0);
+        assert_eq!(Element::Earth.index(), 2);
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(Element::from_name("water"), Some(Element::Water));
+        assert_eq!(Element::from_name("air"), None);
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(Element::names(), vec!["fire", "water", "earth"]);
+    }
+}
@@ -0,0 +1,118 @@
+//! Reads and writes a [`HashMap`] like `JSON.print` and `JSON.parse`.
+//!
+//! Keys are written in their Godot string form and read back into `K`.
+
+use std::hash::{BuildHasher, Hash};
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    collections::HashMap,
+    godot_json::{from_json, to_json, Json, JsonValue},
+    value::{FromValue, ToValue, Value},
+};
+
+/// Writes the map as a JSON object with string keys.
+pub fn serialize<K, V, S, Ser>(
+    map: &HashMap<K, V, S>,
+    serializer: Ser,
+) -> Result<Ser::Ok, Ser::Error>
+where
+    K: ToValue,
+    V: ToValue,
+    Ser: Serializer,
+{
+    Json(&to_json(&map.to_value())).serialize(serializer)
+}
+
+/// Reads a JSON object, parsing the keys back into `K`.
+pub fn deserialize<'de, K, V, S, D>(deserializer: D) -> Result<HashMap<K, V, S>, D::Error>
+where
+    K: Eq + Hash + FromValue,
+    V: FromValue,
+    S: BuildHasher + Default,
+    D: Deserializer<'de>,
+{
+    let JsonValue(value) = JsonValue::deserialize(deserializer)?;
+    let entries = match value {
+        Value::Dictionary(entries) => entries,
+        _ => return Err(D::Error::custom("expected a JSON object")),
+    };
+    let mut map = HashMap::with_capacity_and_hasher(entries.len(), S::default());
+    for (key, value) in entries {
+        map.insert(
+            from_json(&key).map_err(D::Error::custom)?,
+            from_json(&value).map_err(D::Error::custom)?,
+        );
+    }
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use gdnative::core_types::Vector2;
+    use serde::{Deserialize, Serialize};
+
+    use crate::{
+        collections::HashMap,
+        value::{FromValue, FromValueError, ToValue, Value},
+    };
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    struct Cell(i32, i32);
+
+    impl ToValue for Cell {
+        fn to_value(&self) -> Value {
+            Value::Vector2(Vector2::new(self.0 as f32, self.1 as f32))
+        }
+    }
+
+    impl FromValue for Cell {
+        fn from_value(value: &Value) -> Result<Self, FromValueError> {
+            let vector = Vector2::from_value(value)?;
+            Ok(Cell(vector.x as i32, vector.y as i32))
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Tiles {
+        #[serde(with = "crate::godot_json::hash_map")]
+        tiles: HashMap<Cell, i64>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Scores {
+        #[serde(with = "crate::godot_json::hash_map")]
+        ids: HashMap<i64, i64>,
+        #[serde(with = "crate::godot_json::hash_map")]
+        names: HashMap<String, f64>,
+    }
+
+    #[test]
+    fn test_vector2_keys() {
+        let tiles = Tiles {
+            tiles: HashMap::from([(Cell(1, 2), 3)]),
+        };
+        let json = serde_json::to_string(&tiles).unwrap();
+
+        assert_eq!(json, r#"{"tiles":{"(1, 2)":3}}"#);
+        assert_eq!(serde_json::from_str::<Tiles>(&json).unwrap(), tiles);
+    }
+
+    #[test]
+    fn test_from_godot() {
+        let json = r#"{"ids":{"1":3.0,"-2":4.0},"names":{"1":3.0,"True":4.5}}"#;
+        let scores = Scores {
+            ids: HashMap::from([(1, 3), (-2, 4)]),
+            names: HashMap::from([("1".to_string(), 3.0), ("True".to_string(), 4.5)]),
+        };
+
+        assert_eq!(serde_json::from_str::<Scores>(json).unwrap(), scores);
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(serde_json::from_str::<Scores>(r#"{"ids":{"a":1},"names":{}}"#).is_err());
+        assert!(serde_json::from_str::<Scores>(r#"{"ids":[1],"names":{}}"#).is_err());
+    }
+}
@@ -0,0 +1,75 @@
+//! Reads and writes a [`HashSet`] like `JSON.print` and `JSON.parse`.
+
+use std::hash::{BuildHasher, Hash};
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    collections::HashSet,
+    godot_json::{from_json, to_json, Json, JsonValue},
+    value::{FromValue, ToValue, Value},
+};
+
+/// Writes the set as a JSON array.
+pub fn serialize<T, S, Ser>(set: &HashSet<T, S>, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+where
+    T: ToValue,
+    Ser: Serializer,
+{
+    Json(&to_json(&set.to_value())).serialize(serializer)
+}
+
+/// Reads a JSON array, converting floats back into integers where `T`
+/// requires them.
+pub fn deserialize<'de, T, S, D>(deserializer: D) -> Result<HashSet<T, S>, D::Error>
+where
+    T: Eq + Hash + FromValue,
+    S: BuildHasher + Default,
+    D: Deserializer<'de>,
+{
+    let JsonValue(value) = JsonValue::deserialize(deserializer)?;
+    match value {
+        Value::Array(values) => values
+            .iter()
+            .map(|value| from_json(value).map_err(D::Error::custom))
+            .collect(),
+        _ => Err(D::Error::custom("expected a JSON array")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use crate::collections::HashSet;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Unlocked {
+        #[serde(with = "crate::godot_json::hash_set")]
+        levels: HashSet<i64>,
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let unlocked = Unlocked {
+            levels: HashSet::from([4]),
+        };
+        let json = serde_json::to_string(&unlocked).unwrap();
+
+        assert_eq!(json, r#"{"levels":[4]}"#);
+        assert_eq!(serde_json::from_str::<Unlocked>(&json).unwrap(), unlocked);
+    }
+
+    #[test]
+    fn test_from_godot() {
+        let json = r#"{"levels":[4.0,5.0]}"#;
+
+        assert_eq!(
+            serde_json::from_str::<Unlocked>(json).unwrap(),
+            Unlocked {
+                levels: HashSet::from([4, 5])
+            }
+        );
+        assert!(serde_json::from_str::<Unlocked>(r#"{"levels":[4.5]}"#).is_err());
+    }
+}
@@ -0,0 +1,430 @@
+//! Serde adapters matching the JSON written by Godot's `JSON.print` and read
+//! by `JSON.parse`.
+//!
+//! Godot turns every dictionary key into its string form, for example
+//! `Vector2(1, 2)` into `"(1, 2)"`, writes values JSON has no type for as
+//! strings too, and parses every number back as a float. The modules here
+//! write the same JSON and read it back into typed keys and elements, so a
+//! `HashMap<i64, i64>` or a `Vec<Vector2>` survives a trip through GDScript:
+//!
+//! ```
+//! use gdnative::core_types::Vector2;
+//! use gdvariants::{collections::{HashMap, HashSet}, vec::Vec};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Level {
+//!     #[serde(with = "gdvariants::godot_json::hash_map")]
+//!     scores: HashMap<i64, i64>,
+//!     #[serde(with = "gdvariants::godot_json::hash_set")]
+//!     unlocked: HashSet<i64>,
+//!     #[serde(with = "gdvariants::godot_json::vec")]
+//!     path: Vec<Vector2>,
+//! }
+//!
+//! let json = r#"{"scores": {"1": 3.0}, "unlocked": [4.0], "path": ["(1, 2)"]}"#;
+//! let level: Level = serde_json::from_str(json).unwrap();
+//!
+//! assert_eq!(level.scores[&1], 3);
+//! assert!(level.unlocked.contains(&4));
+//! assert_eq!(level.path, [Vector2::new(1.0, 2.0)]);
+//! ```
+//!
+//! Keys and elements are converted through [`Value`](crate::value::Value), so
+//! they need [`ToValue`](crate::value::ToValue) and
+//! [`FromValue`](crate::value::FromValue). A key type that converts to a
+//! `Vector2`, such as a grid cell, is written as `"(x, y)"`. Nested collections
+//! are read leniently: if an element does not convert as is, the keys of its
+//! dictionaries are parsed back into the values Godot wrote them from and the
+//! conversion is retried, and only if that fails as well are its other strings
+//! parsed too.
+
+pub mod hash_map;
+pub mod hash_set;
+pub mod vec;
+
+use std::fmt;
+
+use gdnative::core_types::{Vector2, Vector3};
+use serde::{
+    de::{MapAccess, SeqAccess, Visitor},
+    ser::{SerializeMap, SerializeSeq},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{
+    text,
+    value::{FromValue, FromValueError, Value},
+};
+
+/// Converts `value` into the shape `JSON.print` writes.
+pub(crate) fn to_json(value: &Value) -> Value {
+    match value {
+        Value::Nil | Value::Bool(_) | Value::Int(_) | Value::Real(_) | Value::String(_) => {
+            value.clone()
+        }
+        Value::Dictionary(entries) => Value::Dictionary(
+            entries
+                .iter()
+                .map(|(key, value)| (Value::String(stringify(key)), to_json(value)))
+                .collect(),
+        ),
+        _ => match value.to_array() {
+            Some(values) => Value::Array(values.iter().map(to_json).collect()),
+            None => Value::String(stringify(value)),
+        },
+    }
+}
+
+/// Which strings [`normalize`] parses back into the values Godot stringified.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Strings {
+    Keep,
+    Keys,
+    All,
+}
+
+/// Converts a value read from JSON into a `T`.
+///
+/// Integral floats are read as integers first. If that fails, dictionary
+/// keys, which Godot always stringifies, are parsed back and the conversion
+/// is retried. Other strings are only parsed as a last resort, so string
+/// values that look like numbers stay strings where `T` expects strings.
+pub(crate) fn from_json<T>(value: &Value) -> Result<T, FromValueError>
+where
+    T: FromValue,
+{
+    T::from_value(&normalize(value, Strings::Keep)).or_else(|error| {
+        T::from_value(&normalize(value, Strings::Keys))
+            .or_else(|_| T::from_value(&normalize(value, Strings::All)))
+            .map_err(|_| error)
+    })
+}
+
+fn normalize(value: &Value, strings: Strings) -> Value {
+    match value {
+        Value::Real(real)
+            if real.fract() == 0.0 && (i64::MIN as f64..i64::MAX as f64).contains(real) =>
+        {
+            Value::Int(*real as i64)
+        }
+        Value::String(string) if strings == Strings::All => unstringify(string),
+        Value::Array(values) => Value::Array(
+            values
+                .iter()
+                .map(|value| normalize(value, strings))
+                .collect(),
+        ),
+        Value::Dictionary(entries) => Value::Dictionary(
+            entries
+                .iter()
+                .map(|(key, value)| {
+                    let key = match key {
+                        Value::String(key) if strings != Strings::Keep => unstringify(key),
+                        _ => normalize(key, strings),
+                    };
+                    (key, normalize(value, strings))
+                })
+                .collect(),
+        ),
+        _ => value.clone(),
+    }
+}
+
+/// Formats a number like Godot 3's `String::num`: up to 14 significant
+/// digits, without trailing zeros.
+fn num(value: f64) -> String {
+    if value == 0.0 {
+        return "0".to_string();
+    }
+    if !value.is_finite() {
+        return text::write_value(&Value::Real(value));
+    }
+    let decimals = (14 - value.abs().log10().floor() as i32).clamp(0, 16) as usize;
+    let fixed = format!("{:.*}", decimals, value);
+    if fixed.contains('.') {
+        fixed
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    } else {
+        fixed
+    }
+}
+
+/// Converts `value` to a string like Godot's `String(value)`.
+///
+/// Types whose string form cannot be read back unambiguously are written in
+/// `var2str` syntax instead.
+pub(crate) fn stringify(value: &Value) -> String {
+    match value {
+        Value::Nil => "Null".to_string(),
+        Value::Bool(true) => "True".to_string(),
+        Value::Bool(false) => "False".to_string(),
+        Value::Int(int) => int.to_string(),
+        Value::Real(real) => num(*real),
+        Value::String(string) | Value::NodePath(string) => string.clone(),
+        Value::Vector2(vector) => {
+            format!("({}, {})", num(vector.x.into()), num(vector.y.into()))
+        }
+        Value::Vector3(vector) => format!(
+            "({}, {}, {})",
+            num(vector.x.into()),
+            num(vector.y.into()),
+            num(vector.z.into())
+        ),
+        _ => text::write_value(value),
+    }
+}
+
+/// Reads back a string written by [`stringify`], or keeps it as a string.
+pub(crate) fn unstringify(string: &str) -> Value {
+    match string {
+        "Null" => return Value::Nil,
+        "True" => return Value::Bool(true),
+        "False" => return Value::Bool(false),
+        _ => {}
+    }
+    if let Ok(int) = string.parse() {
+        return Value::Int(int);
+    }
+    if let Ok(real) = string.parse() {
+        return normalize(&Value::Real(real), Strings::Keep);
+    }
+    if let Some(components) = string
+        .strip_prefix('(')
+        .and_then(|inner| inner.strip_suffix(')'))
+        .and_then(|inner| {
+            inner
+                .split(", ")
+                .map(|component| component.parse::<f32>().ok())
+                .collect::<Option<std::vec::Vec<f32>>>()
+        })
+    {
+        match components.as_slice() {
+            [x, y] => return Value::Vector2(Vector2::new(*x, *y)),
+            [x, y, z] => return Value::Vector3(Vector3::new(*x, *y, *z)),
+            _ => {}
+        }
+    }
+    text::parse_value(string).unwrap_or_else(|_| Value::String(string.to_string()))
+}
+
+/// Serializes a value produced by [`to_json`].
+pub(crate) struct Json<'a>(pub(crate) &'a Value);
+
+impl Serialize for Json<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.0 {
+            Value::Nil => serializer.serialize_unit(),
+            Value::Bool(value) => serializer.serialize_bool(*value),
+            Value::Int(value) => serializer.serialize_i64(*value),
+            Value::Real(value) => serializer.serialize_f64(*value),
+            Value::String(value) => serializer.serialize_str(value),
+            Value::Array(values) => {
+                let mut seq = serializer.serialize_seq(Some(values.len()))?;
+                for value in values {
+                    seq.serialize_element(&Json(value))?;
+                }
+                seq.end()
+            }
+            Value::Dictionary(entries) => {
+                let mut map = serializer.serialize_map(Some(entries.len()))?;
+                for (key, value) in entries {
+                    map.serialize_entry(&Json(key), &Json(value))?;
+                }
+                map.end()
+            }
+            value => serializer.serialize_str(&stringify(value)),
+        }
+    }
+}
+
+/// A value deserialized from JSON.
+pub(crate) struct JsonValue(pub(crate) Value);
+
+impl<'de> Deserialize<'de> for JsonValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(JsonVisitor).map(JsonValue)
+    }
+}
+
+struct JsonVisitor;
+
+impl<'de> Visitor<'de> for JsonVisitor {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a JSON value")
+    }
+
+    fn visit_unit<E>(self) -> Result<Value, E> {
+        Ok(Value::Nil)
+    }
+
+    fn visit_none<E>(self) -> Result<Value, E> {
+        Ok(Value::Nil)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Value, E> {
+        Ok(Value::Bool(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Value, E> {
+        Ok(Value::Int(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Value, E> {
+        Ok(i64::try_from(v).map_or(Value::Real(v as f64), Value::Int))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Value, E> {
+        Ok(Value::Real(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Value, E> {
+        Ok(Value::String(v.to_string()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Value, E> {
+        Ok(Value::String(v))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut values = std::vec::Vec::new();
+        while let Some(JsonValue(value)) = seq.next_element()? {
+            values.push(value);
+        }
+        Ok(Value::Array(values))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entries = std::vec::Vec::new();
+        while let Some((JsonValue(key), JsonValue(value))) = map.next_entry()? {
+            entries.push((key, value));
+        }
+        Ok(Value::Dictionary(entries))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gdnative::core_types::{Vector2, Vector3};
+
+    use crate::{
+        collections::HashMap,
+        godot_json::{from_json, stringify, to_json, unstringify},
+        value::Value,
+        vec::Vec,
+    };
+
+    #[test]
+    fn test_stringify() {
+        assert_eq!(stringify(&Value::Int(-3)), "-3");
+        assert_eq!(stringify(&Value::Real(1.0)), "1");
+        assert_eq!(stringify(&Value::Real(0.1)), "0.1");
+        assert_eq!(stringify(&Value::Bool(true)), "True");
+        assert_eq!(
+            stringify(&Value::Vector2(Vector2::new(1.0, 2.5))),
+            "(1, 2.5)"
+        );
+    }
+
+    #[test]
+    fn test_unstringify() {
+        assert_eq!(unstringify("-3"), Value::Int(-3));
+        assert_eq!(unstringify("2.5"), Value::Real(2.5));
+        assert_eq!(unstringify("False"), Value::Bool(false));
+        assert_eq!(
+            unstringify("(1, 2, 3)"),
+            Value::Vector3(Vector3::new(1.0, 2.0, 3.0))
+        );
+        assert_eq!(unstringify("abc"), Value::String("abc".to_string()));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let value = Value::Dictionary(vec![(
+            Value::Int(1),
+            Value::PoolVector2Array(vec![Vector2::new(1.0, 2.0)]),
+        )]);
+
+        assert_eq!(
+            to_json(&value),
+            Value::Dictionary(vec![(
+                Value::String("1".to_string()),
+                Value::Array(vec![Value::String("(1, 2)".to_string())])
+            )])
+        );
+    }
+
+    #[test]
+    fn test_from_json_nested() {
+        let value = Value::Array(vec![Value::Dictionary(vec![(
+            Value::String("2".to_string()),
+            Value::Real(3.0),
+        )])]);
+
+        assert_eq!(
+            from_json::<Vec<HashMap<i64, i64>>>(&value),
+            Ok(Vec::from(vec![HashMap::from([(2, 3)])]))
+        );
+        assert_eq!(
+            from_json::<Vec<HashMap<String, f64>>>(&value),
+            Ok(Vec::from(vec![HashMap::from([("2".to_string(), 3.0)])]))
+        );
+    }
+
+    #[test]
+    fn test_from_json_nested_strings() {
+        let value = Value::Array(vec![Value::Dictionary(vec![
+            (
+                Value::String("1".to_string()),
+                Value::String("42".to_string()),
+            ),
+            (
+                Value::String("2".to_string()),
+                Value::String("True".to_string()),
+            ),
+        ])]);
+
+        assert_eq!(
+            from_json::<Vec<HashMap<i64, String>>>(&value),
+            Ok(Vec::from(vec![HashMap::from([
+                (1, "42".to_string()),
+                (2, "True".to_string())
+            ])]))
+        );
+        assert_eq!(
+            from_json::<Vec<HashMap<i64, Vector2>>>(&Value::Array(vec![Value::Dictionary(vec![
+                (
+                    Value::String("1".to_string()),
+                    Value::String("(1, 2)".to_string()),
+                )
+            ])])),
+            Ok(Vec::from(vec![HashMap::from([(
+                1,
+                Vector2::new(1.0, 2.0)
+            )])]))
+        );
+    }
+}
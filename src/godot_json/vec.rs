@@ -0,0 +1,59 @@
+//! Reads and writes a [`Vec`] like `JSON.print` and `JSON.parse`.
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    godot_json::{from_json, to_json, Json, JsonValue},
+    value::{FromValue, ToValue, Value},
+    vec::Vec,
+};
+
+/// Writes the vector as a JSON array.
+pub fn serialize<T, Ser>(vec: &Vec<T>, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+where
+    T: ToValue,
+    Ser: Serializer,
+{
+    Json(&to_json(&vec.to_value())).serialize(serializer)
+}
+
+/// Reads a JSON array, converting the elements back into `T`.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    T: FromValue,
+    D: Deserializer<'de>,
+{
+    let JsonValue(value) = JsonValue::deserialize(deserializer)?;
+    match value {
+        Value::Array(values) => values
+            .iter()
+            .map(|value| from_json(value).map_err(D::Error::custom))
+            .collect(),
+        _ => Err(D::Error::custom("expected a JSON array")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gdnative::core_types::Vector2;
+    use serde::{Deserialize, Serialize};
+
+    use crate::vec::Vec;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Path {
+        #[serde(with = "crate::godot_json::vec")]
+        points: Vec<Vector2>,
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let path = Path {
+            points: Vec::from(vec![Vector2::new(1.0, 2.0), Vector2::new(0.5, 0.0)]),
+        };
+        let json = serde_json::to_string(&path).unwrap();
+
+        assert_eq!(json, r#"{"points":["(1, 2)","(0.5, 0)"]}"#);
+        assert_eq!(serde_json::from_str::<Path>(&json).unwrap(), path);
+    }
+}
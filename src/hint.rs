@@ -0,0 +1,74 @@
+//! Export hints for the collection types.
+//!
+//! The hints are passed to [`Export::export_info`](gdnative::export::Export::export_info),
+//! usually through the property builder:
+//!
+//! ```
+//! use gdnative::export::hint::{IntHint, RangeHint};
+//! use gdvariants::hint::CollectionHint;
+//!
+//! // Restricts every element of an exported `Vec<i64>` to 0..100.
+//! let hint: CollectionHint<IntHint<i64>> =
+//!     CollectionHint::with_element(IntHint::Range(RangeHint::new(0, 100)));
+//! ```
+
+/// Hint for exported `Vec` and `HashSet` properties.
+///
+/// `HashMap` takes no hint, since Godot 3 has no hint for the keys or values
+/// of a dictionary.
+///
+/// Holds an optional hint for the elements. Without one, the default export
+/// info of the element type is used.
+#[derive(Debug)]
+pub struct CollectionHint<E> {
+    element: Option<E>,
+}
+
+impl<E> CollectionHint<E> {
+    /// Creates a hint that uses the default export info of the element type.
+    #[inline]
+    pub fn new() -> CollectionHint<E> {
+        CollectionHint { element: None }
+    }
+
+    /// Creates a hint that applies `hint` to every element.
+    #[inline]
+    pub fn with_element(hint: E) -> CollectionHint<E> {
+        CollectionHint {
+            element: Some(hint),
+        }
+    }
+
+    /// Returns the element hint.
+    #[inline]
+    pub fn element(&self) -> Option<&E> {
+        self.element.as_ref()
+    }
+
+    /// Returns the element hint, consuming the collection hint.
+    #[inline]
+    pub fn into_element(self) -> Option<E> {
+        self.element
+    }
+}
+
+impl<E> Default for CollectionHint<E> {
+    #[inline]
+    fn default() -> CollectionHint<E> {
+        CollectionHint::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hint::CollectionHint;
+
+    #[test]
+    fn test_collection_hint() {
+        let hint: CollectionHint<i32> = CollectionHint::with_element(4);
+
+        assert_eq!(hint.element(), Some(&4));
+        assert_eq!(hint.into_element(), Some(4));
+        assert_eq!(CollectionHint::<i32>::default().element(), None);
+    }
+}
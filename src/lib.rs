@@ -1,2 +1,34 @@
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+#![cfg_attr(feature = "nightly", feature(hash_set_entry, map_try_insert))]
+
+#[macro_use]
+mod macros;
+
+pub mod cached;
 pub mod collections;
+pub mod config_file;
+pub mod enums;
+#[cfg(feature = "serde")]
+pub mod godot_json;
+pub mod hint;
+pub mod marshal;
+pub mod property;
+#[cfg(test)]
+mod test_util;
+pub mod text;
+pub mod tracked;
+pub mod value;
 pub mod vec;
+pub mod versioned;
+pub mod with;
+
+#[cfg(feature = "derive")]
+pub use gdvariants_derive::GdCollection;
+
+/// Paths used by the code of the derive macros. Not public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use gdnative;
+    #[cfg(feature = "serde")]
+    pub use serde;
+}
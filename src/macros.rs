@@ -0,0 +1,148 @@
+/// Creates a [`Vec`](crate::vec::Vec) containing the arguments.
+///
+/// Takes the same forms as the std `vec!` macro.
+///
+/// # Examples
+///
+/// ```
+/// use gdvariants::{gdvec, vec::Vec};
+///
+/// let v: Vec<i32> = gdvec![1, 2, 3];
+/// assert_eq!(v, [1, 2, 3]);
+///
+/// let v: Vec<i32> = gdvec![0; 4];
+/// assert_eq!(v, [0, 0, 0, 0]);
+/// ```
+#[macro_export]
+macro_rules! gdvec {
+    () => {
+        $crate::vec::Vec::new()
+    };
+    ($elem:expr; $n:expr) => {
+        $crate::vec::Vec::from(::std::vec![$elem; $n])
+    };
+    ($($x:expr),+ $(,)?) => {
+        $crate::vec::Vec::from(::std::vec![$($x),+])
+    };
+}
+
+/// Creates a [`HashMap`](crate::collections::HashMap) containing the
+/// arguments, with the capacity for exactly that many entries.
+///
+/// # Examples
+///
+/// ```
+/// use gdvariants::{collections::HashMap, gdhashmap};
+///
+/// let map: HashMap<&str, i32> = gdhashmap! {
+///     "strength" => 10,
+///     "agility" => 12,
+/// };
+/// assert_eq!(map["agility"], 12);
+/// ```
+#[macro_export]
+macro_rules! gdhashmap {
+    () => {
+        $crate::collections::HashMap::new()
+    };
+    ($($key:expr => $value:expr),+ $(,)?) => {{
+        let mut map =
+            $crate::collections::HashMap::with_capacity($crate::__gdvariants_count!($($key),+));
+        $(
+            map.insert($key, $value);
+        )+
+        map
+    }};
+}
+
+/// Creates a [`HashSet`](crate::collections::HashSet) containing the
+/// arguments, with the capacity for exactly that many elements.
+///
+/// # Examples
+///
+/// ```
+/// use gdvariants::{collections::HashSet, gdhashset};
+///
+/// let set: HashSet<&str> = gdhashset!["red", "blue"];
+/// assert!(set.contains("blue"));
+/// ```
+#[macro_export]
+macro_rules! gdhashset {
+    () => {
+        $crate::collections::HashSet::new()
+    };
+    ($($value:expr),+ $(,)?) => {{
+        let mut set =
+            $crate::collections::HashSet::with_capacity($crate::__gdvariants_count!($($value),+));
+        $(
+            set.insert($value);
+        )+
+        set
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __gdvariants_count {
+    ($($x:expr),*) => {
+        <[()]>::len(&[$($crate::__gdvariants_count!(@unit $x)),*])
+    };
+    (@unit $x:expr) => {
+        ()
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        collections::{HashMap, HashSet},
+        vec::Vec,
+    };
+
+    #[test]
+    fn test_gdvec() {
+        let empty: Vec<i32> = gdvec![];
+        let repeated: Vec<i32> = gdvec![7; 3];
+        let listed: Vec<i32> = gdvec![2, 1, 3,];
+
+        assert!(empty.is_empty());
+        assert_eq!(repeated, vec![7, 7, 7]);
+        assert_eq!(listed, vec![2, 1, 3]);
+    }
+
+    #[test]
+    fn test_gdhashmap() {
+        let empty: HashMap<i32, i32> = gdhashmap! {};
+        let map = gdhashmap! { 2 => 4, 1 => 2, 3 => 6 };
+
+        assert!(empty.is_empty());
+        assert_eq!(map, HashMap::from([(2, 4), (1, 2), (3, 6)]));
+        assert!(map.capacity() >= 3);
+    }
+
+    #[test]
+    fn test_gdhashset() {
+        let empty: HashSet<i32> = gdhashset![];
+        let set = gdhashset![2, 1, 3, 1];
+
+        assert!(empty.is_empty());
+        assert_eq!(set, HashSet::from([2, 1, 3]));
+    }
+
+    #[test]
+    fn test_default() {
+        struct Stats {
+            values: HashMap<&'static str, i32>,
+        }
+
+        impl Default for Stats {
+            fn default() -> Self {
+                Stats {
+                    values: gdhashmap! { "health" => 100 },
+                }
+            }
+        }
+
+        assert_eq!(Stats::default().values["health"], 100);
+    }
+}
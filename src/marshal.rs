@@ -0,0 +1,794 @@
+//! Godot 3's binary `Variant` format, as produced by `var2bytes` and read by
+//! `bytes2var`.
+//!
+//! Works on [`Value`]s, so it runs without the engine, for example on a
+//! dedicated server that talks to GDScript clients.
+//!
+//! ```
+//! use gdvariants::{marshal, vec::Vec};
+//!
+//! let bytes = marshal::encode(&Vec::from(vec![1, 2])).unwrap();
+//! assert_eq!(
+//!     bytes,
+//!     [19, 0, 0, 0, 2, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 2, 0, 0, 0]
+//! );
+//!
+//! let vec: Vec<i32> = marshal::decode(&bytes).unwrap();
+//! assert_eq!(vec, [1, 2]);
+//! ```
+//!
+//! Objects and RIDs only exist inside the engine and are not supported.
+//! Neither are resource references, which only the engine can resolve.
+
+use std::{error::Error, fmt};
+
+use gdnative::core_types::{
+    Aabb, Basis, Color, Plane, Quat, Rect2, Transform, Transform2D, Vector2, Vector3,
+};
+
+use crate::value::{FromValue, FromValueError, ResourceError, ToValue, Value};
+
+const TYPE_NIL: u32 = 0;
+const TYPE_BOOL: u32 = 1;
+const TYPE_INT: u32 = 2;
+const TYPE_REAL: u32 = 3;
+const TYPE_STRING: u32 = 4;
+const TYPE_VECTOR2: u32 = 5;
+const TYPE_RECT2: u32 = 6;
+const TYPE_VECTOR3: u32 = 7;
+const TYPE_TRANSFORM2D: u32 = 8;
+const TYPE_PLANE: u32 = 9;
+const TYPE_QUAT: u32 = 10;
+const TYPE_AABB: u32 = 11;
+const TYPE_BASIS: u32 = 12;
+const TYPE_TRANSFORM: u32 = 13;
+const TYPE_COLOR: u32 = 14;
+const TYPE_NODE_PATH: u32 = 15;
+const TYPE_DICTIONARY: u32 = 18;
+const TYPE_ARRAY: u32 = 19;
+const TYPE_POOL_BYTE_ARRAY: u32 = 20;
+const TYPE_POOL_INT_ARRAY: u32 = 21;
+const TYPE_POOL_REAL_ARRAY: u32 = 22;
+const TYPE_POOL_STRING_ARRAY: u32 = 23;
+const TYPE_POOL_VECTOR2_ARRAY: u32 = 24;
+const TYPE_POOL_VECTOR3_ARRAY: u32 = 25;
+const TYPE_POOL_COLOR_ARRAY: u32 = 26;
+
+/// Marks an `int` or `float` stored with 64 bits.
+const FLAG_64: u32 = 1 << 16;
+/// Marks the new `NodePath` encoding, which is the only one Godot 3 writes.
+const NODE_PATH_NEW_FORMAT: u32 = 0x8000_0000;
+/// Marks a shared `Array` or `Dictionary` in the element count.
+const COUNT_MASK: u32 = 0x7fff_ffff;
+/// How deep `Array`s and `Dictionary`s may be nested. Godot stops at 1024,
+/// which overflows a 2 MiB thread stack in debug builds.
+const MAX_DEPTH: usize = 512;
+
+/// Encodes `value` the way `var2bytes` does.
+///
+/// Fails if the value holds a resource reference.
+pub fn encode<T>(value: &T) -> Result<std::vec::Vec<u8>, ResourceError>
+where
+    T: ToValue + ?Sized,
+{
+    encode_value(&value.to_value())
+}
+
+/// Decodes a `T` from the output of `var2bytes`.
+pub fn decode<T>(bytes: &[u8]) -> Result<T, DecodeError>
+where
+    T: FromValue,
+{
+    T::from_value(&decode_value(bytes)?).map_err(DecodeError::Value)
+}
+
+/// Encodes `value` the way `var2bytes` does.
+///
+/// Fails if the value holds a resource reference.
+pub fn encode_value(value: &Value) -> Result<std::vec::Vec<u8>, ResourceError> {
+    let mut writer = Writer::default();
+    writer.value(value)?;
+    Ok(writer.bytes)
+}
+
+/// Decodes the output of `var2bytes`.
+///
+/// Fails if the bytes do not hold exactly one value, or if `Array`s and
+/// `Dictionary`s are nested more than 512 deep.
+pub fn decode_value(bytes: &[u8]) -> Result<Value, DecodeError> {
+    let mut reader = Reader {
+        bytes,
+        offset: 0,
+        depth: 0,
+    };
+    let value = reader.value()?;
+    if reader.offset != bytes.len() {
+        return Err(DecodeError::TrailingBytes {
+            offset: reader.offset,
+        });
+    }
+    Ok(value)
+}
+
+/// Error returned by [`decode`] and [`decode_value`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The bytes end in the middle of a value.
+    UnexpectedEnd { offset: usize },
+    /// The type id is unknown or not supported without the engine.
+    UnsupportedType { offset: usize, type_id: u32 },
+    /// A string is not valid UTF-8.
+    InvalidUtf8 { offset: usize },
+    /// There are bytes left after the value.
+    TrailingBytes { offset: usize },
+    /// `Array`s or `Dictionary`s are nested too deeply.
+    TooDeep { offset: usize },
+    /// The value could not be converted into the requested type.
+    Value(FromValueError),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::UnexpectedEnd { offset } => {
+                write!(f, "unexpected end of data at byte {}", offset)
+            }
+            DecodeError::UnsupportedType { offset, type_id } => {
+                write!(f, "unsupported type {} at byte {}", type_id, offset)
+            }
+            DecodeError::InvalidUtf8 { offset } => {
+                write!(f, "invalid UTF-8 in string at byte {}", offset)
+            }
+            DecodeError::TrailingBytes { offset } => {
+                write!(f, "trailing bytes after the value at byte {}", offset)
+            }
+            DecodeError::TooDeep { offset } => {
+                write!(f, "values nested too deeply at byte {}", offset)
+            }
+            DecodeError::Value(error) => error.fmt(f),
+        }
+    }
+}
+
+impl Error for DecodeError {}
+
+#[derive(Default)]
+struct Writer {
+    bytes: std::vec::Vec<u8>,
+}
+
+impl Writer {
+    fn u32(&mut self, value: u32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn f32(&mut self, value: f32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn len(&mut self, len: usize) {
+        self.u32(len as u32);
+    }
+
+    fn pad(&mut self) {
+        while self.bytes.len() % 4 != 0 {
+            self.bytes.push(0);
+        }
+    }
+
+    fn string(&mut self, value: &str) {
+        self.len(value.len());
+        self.bytes.extend_from_slice(value.as_bytes());
+        self.pad();
+    }
+
+    fn vector2(&mut self, value: Vector2) {
+        self.f32(value.x);
+        self.f32(value.y);
+    }
+
+    fn vector3(&mut self, value: Vector3) {
+        self.f32(value.x);
+        self.f32(value.y);
+        self.f32(value.z);
+    }
+
+    fn basis(&mut self, value: Basis) {
+        for row in value.elements {
+            self.vector3(row);
+        }
+    }
+
+    fn color(&mut self, value: Color) {
+        self.f32(value.r);
+        self.f32(value.g);
+        self.f32(value.b);
+        self.f32(value.a);
+    }
+
+    fn node_path(&mut self, path: &str) {
+        let (names, subnames) = path.split_once(':').unwrap_or((path, ""));
+        let names: std::vec::Vec<&str> = names.split('/').filter(|s| !s.is_empty()).collect();
+        let subnames: std::vec::Vec<&str> = subnames.split(':').filter(|s| !s.is_empty()).collect();
+        self.u32(names.len() as u32 | NODE_PATH_NEW_FORMAT);
+        self.len(subnames.len());
+        self.u32(u32::from(path.starts_with('/')));
+        for name in names.into_iter().chain(subnames) {
+            self.string(name);
+        }
+    }
+
+    fn value(&mut self, value: &Value) -> Result<(), ResourceError> {
+        match value {
+            Value::Nil => self.u32(TYPE_NIL),
+            Value::Bool(value) => {
+                self.u32(TYPE_BOOL);
+                self.u32(u32::from(*value));
+            }
+            Value::Int(value) => match i32::try_from(*value) {
+                Ok(value) => {
+                    self.u32(TYPE_INT);
+                    self.bytes.extend_from_slice(&value.to_le_bytes());
+                }
+                Err(_) => {
+                    self.u32(TYPE_INT | FLAG_64);
+                    self.bytes.extend_from_slice(&value.to_le_bytes());
+                }
+            },
+            Value::Real(value) => {
+                if f64::from(*value as f32) == *value {
+                    self.u32(TYPE_REAL);
+                    self.f32(*value as f32);
+                } else {
+                    self.u32(TYPE_REAL | FLAG_64);
+                    self.bytes.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+            Value::String(value) => {
+                self.u32(TYPE_STRING);
+                self.string(value);
+            }
+            Value::Vector2(value) => {
+                self.u32(TYPE_VECTOR2);
+                self.vector2(*value);
+            }
+            Value::Rect2(value) => {
+                self.u32(TYPE_RECT2);
+                self.vector2(value.position);
+                self.vector2(value.size);
+            }
+            Value::Vector3(value) => {
+                self.u32(TYPE_VECTOR3);
+                self.vector3(*value);
+            }
+            Value::Transform2D(value) => {
+                self.u32(TYPE_TRANSFORM2D);
+                self.vector2(value.a);
+                self.vector2(value.b);
+                self.vector2(value.origin);
+            }
+            Value::Plane(value) => {
+                self.u32(TYPE_PLANE);
+                self.vector3(value.normal);
+                self.f32(value.d);
+            }
+            Value::Quat(value) => {
+                self.u32(TYPE_QUAT);
+                self.f32(value.x);
+                self.f32(value.y);
+                self.f32(value.z);
+                self.f32(value.w);
+            }
+            Value::Aabb(value) => {
+                self.u32(TYPE_AABB);
+                self.vector3(value.position);
+                self.vector3(value.size);
+            }
+            Value::Basis(value) => {
+                self.u32(TYPE_BASIS);
+                self.basis(*value);
+            }
+            Value::Transform(value) => {
+                self.u32(TYPE_TRANSFORM);
+                self.basis(value.basis);
+                self.vector3(value.origin);
+            }
+            Value::Color(value) => {
+                self.u32(TYPE_COLOR);
+                self.color(*value);
+            }
+            Value::NodePath(value) => {
+                self.u32(TYPE_NODE_PATH);
+                self.node_path(value);
+            }
+            Value::Dictionary(entries) => {
+                self.u32(TYPE_DICTIONARY);
+                self.len(entries.len());
+                for (key, value) in entries {
+                    self.value(key)?;
+                    self.value(value)?;
+                }
+            }
+            Value::Array(values) => {
+                self.u32(TYPE_ARRAY);
+                self.len(values.len());
+                for value in values {
+                    self.value(value)?;
+                }
+            }
+            Value::PoolByteArray(values) => {
+                self.u32(TYPE_POOL_BYTE_ARRAY);
+                self.len(values.len());
+                self.bytes.extend_from_slice(values);
+                self.pad();
+            }
+            Value::PoolIntArray(values) => {
+                self.u32(TYPE_POOL_INT_ARRAY);
+                self.len(values.len());
+                for value in values {
+                    self.bytes.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+            Value::PoolRealArray(values) => {
+                self.u32(TYPE_POOL_REAL_ARRAY);
+                self.len(values.len());
+                for value in values {
+                    self.f32(*value);
+                }
+            }
+            Value::PoolStringArray(values) => {
+                self.u32(TYPE_POOL_STRING_ARRAY);
+                self.len(values.len());
+                for value in values {
+                    self.len(value.len() + 1);
+                    self.bytes.extend_from_slice(value.as_bytes());
+                    self.bytes.push(0);
+                    self.pad();
+                }
+            }
+            Value::PoolVector2Array(values) => {
+                self.u32(TYPE_POOL_VECTOR2_ARRAY);
+                self.len(values.len());
+                for value in values {
+                    self.vector2(*value);
+                }
+            }
+            Value::PoolVector3Array(values) => {
+                self.u32(TYPE_POOL_VECTOR3_ARRAY);
+                self.len(values.len());
+                for value in values {
+                    self.vector3(*value);
+                }
+            }
+            Value::PoolColorArray(values) => {
+                self.u32(TYPE_POOL_COLOR_ARRAY);
+                self.len(values.len());
+                for value in values {
+                    self.color(*value);
+                }
+            }
+            Value::Resource(resource) => {
+                return Err(ResourceError {
+                    resource: resource.clone(),
+                })
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    /// How many `Array`s and `Dictionary`s contain the current value.
+    depth: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or(DecodeError::UnexpectedEnd {
+                offset: self.bytes.len(),
+            })?;
+        let bytes = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn u32(&mut self) -> Result<u32, DecodeError> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    fn f32(&mut self) -> Result<f32, DecodeError> {
+        self.array().map(f32::from_le_bytes)
+    }
+
+    /// Reads an element count, and reserves no more than the remaining bytes
+    /// could hold, so a corrupt count cannot allocate unbounded memory.
+    fn len<T>(&mut self, element_size: usize) -> Result<(usize, std::vec::Vec<T>), DecodeError> {
+        let len = (self.u32()? & COUNT_MASK) as usize;
+        let remaining = (self.bytes.len() - self.offset) / element_size;
+        Ok((len, std::vec::Vec::with_capacity(len.min(remaining))))
+    }
+
+    fn skip_padding(&mut self) -> Result<(), DecodeError> {
+        let padding = (4 - self.offset % 4) % 4;
+        self.take(padding).map(|_| ())
+    }
+
+    fn utf8(&mut self, len: usize) -> Result<String, DecodeError> {
+        let offset = self.offset;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| DecodeError::InvalidUtf8 { offset })
+    }
+
+    fn string(&mut self) -> Result<String, DecodeError> {
+        let len = self.u32()? as usize;
+        let string = self.utf8(len)?;
+        self.skip_padding()?;
+        Ok(string)
+    }
+
+    fn vector2(&mut self) -> Result<Vector2, DecodeError> {
+        Ok(Vector2::new(self.f32()?, self.f32()?))
+    }
+
+    fn vector3(&mut self) -> Result<Vector3, DecodeError> {
+        Ok(Vector3::new(self.f32()?, self.f32()?, self.f32()?))
+    }
+
+    fn basis(&mut self) -> Result<Basis, DecodeError> {
+        Ok(Basis {
+            elements: [self.vector3()?, self.vector3()?, self.vector3()?],
+        })
+    }
+
+    fn color(&mut self) -> Result<Color, DecodeError> {
+        Ok(Color {
+            r: self.f32()?,
+            g: self.f32()?,
+            b: self.f32()?,
+            a: self.f32()?,
+        })
+    }
+
+    fn node_path(&mut self, offset: usize) -> Result<String, DecodeError> {
+        let name_count = self.u32()?;
+        if name_count & NODE_PATH_NEW_FORMAT == 0 {
+            return Err(DecodeError::UnsupportedType {
+                offset,
+                type_id: TYPE_NODE_PATH,
+            });
+        }
+        let name_count = (name_count & !NODE_PATH_NEW_FORMAT) as usize;
+        let mut subname_count = self.u32()? as usize;
+        let flags = self.u32()?;
+        // Old paths stored the property apart from the subnames.
+        if flags & 2 != 0 {
+            subname_count += 1;
+        }
+        let mut path = String::new();
+        if flags & 1 != 0 {
+            path.push('/');
+        }
+        for index in 0..name_count {
+            if index > 0 {
+                path.push('/');
+            }
+            path.push_str(&self.string()?);
+        }
+        for _ in 0..subname_count {
+            path.push(':');
+            path.push_str(&self.string()?);
+        }
+        Ok(path)
+    }
+
+    /// Steps into an `Array` or `Dictionary` starting at `offset`.
+    fn enter(&mut self, offset: usize) -> Result<(), DecodeError> {
+        if self.depth == MAX_DEPTH {
+            return Err(DecodeError::TooDeep { offset });
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn value(&mut self) -> Result<Value, DecodeError> {
+        let offset = self.offset;
+        let header = self.u32()?;
+        Ok(match header & 0xff {
+            TYPE_DICTIONARY => {
+                let (len, mut entries) = self.len(8)?;
+                self.enter(offset)?;
+                for _ in 0..len {
+                    entries.push((self.value()?, self.value()?));
+                }
+                self.depth -= 1;
+                Value::Dictionary(entries)
+            }
+            TYPE_ARRAY => {
+                let (len, mut values) = self.len(4)?;
+                self.enter(offset)?;
+                for _ in 0..len {
+                    values.push(self.value()?);
+                }
+                self.depth -= 1;
+                Value::Array(values)
+            }
+            _ => self.leaf(offset, header)?,
+        })
+    }
+
+    /// Reads a value that cannot contain other values.
+    ///
+    /// Kept out of `value` so the frames of nested containers stay small.
+    #[inline(never)]
+    fn leaf(&mut self, offset: usize, header: u32) -> Result<Value, DecodeError> {
+        let wide = header & FLAG_64 != 0;
+        Ok(match header & 0xff {
+            TYPE_NIL => Value::Nil,
+            TYPE_BOOL => Value::Bool(self.u32()? != 0),
+            TYPE_INT if wide => Value::Int(i64::from_le_bytes(self.array()?)),
+            TYPE_INT => Value::Int(i32::from_le_bytes(self.array()?).into()),
+            TYPE_REAL if wide => Value::Real(f64::from_le_bytes(self.array()?)),
+            TYPE_REAL => Value::Real(self.f32()?.into()),
+            TYPE_STRING => Value::String(self.string()?),
+            TYPE_VECTOR2 => Value::Vector2(self.vector2()?),
+            TYPE_RECT2 => Value::Rect2(Rect2 {
+                position: self.vector2()?,
+                size: self.vector2()?,
+            }),
+            TYPE_VECTOR3 => Value::Vector3(self.vector3()?),
+            TYPE_TRANSFORM2D => Value::Transform2D(Transform2D {
+                a: self.vector2()?,
+                b: self.vector2()?,
+                origin: self.vector2()?,
+            }),
+            TYPE_PLANE => Value::Plane(Plane {
+                normal: self.vector3()?,
+                d: self.f32()?,
+            }),
+            TYPE_QUAT => Value::Quat(Quat::new(
+                self.f32()?,
+                self.f32()?,
+                self.f32()?,
+                self.f32()?,
+            )),
+            TYPE_AABB => Value::Aabb(Aabb {
+                position: self.vector3()?,
+                size: self.vector3()?,
+            }),
+            TYPE_BASIS => Value::Basis(self.basis()?),
+            TYPE_TRANSFORM => Value::Transform(Transform {
+                basis: self.basis()?,
+                origin: self.vector3()?,
+            }),
+            TYPE_COLOR => Value::Color(self.color()?),
+            TYPE_NODE_PATH => Value::NodePath(self.node_path(offset)?),
+            TYPE_POOL_BYTE_ARRAY => {
+                let len = self.u32()? as usize;
+                let values = self.take(len)?.to_vec();
+                self.skip_padding()?;
+                Value::PoolByteArray(values)
+            }
+            TYPE_POOL_INT_ARRAY => {
+                let (len, mut values) = self.len(4)?;
+                for _ in 0..len {
+                    values.push(i32::from_le_bytes(self.array()?));
+                }
+                Value::PoolIntArray(values)
+            }
+            TYPE_POOL_REAL_ARRAY => {
+                let (len, mut values) = self.len(4)?;
+                for _ in 0..len {
+                    values.push(self.f32()?);
+                }
+                Value::PoolRealArray(values)
+            }
+            TYPE_POOL_STRING_ARRAY => {
+                let (len, mut values) = self.len(4)?;
+                for _ in 0..len {
+                    let size = self.u32()? as usize;
+                    let mut value = self.utf8(size)?;
+                    if value.ends_with('\0') {
+                        value.pop();
+                    }
+                    self.skip_padding()?;
+                    values.push(value);
+                }
+                Value::PoolStringArray(values)
+            }
+            TYPE_POOL_VECTOR2_ARRAY => {
+                let (len, mut values) = self.len(8)?;
+                for _ in 0..len {
+                    values.push(self.vector2()?);
+                }
+                Value::PoolVector2Array(values)
+            }
+            TYPE_POOL_VECTOR3_ARRAY => {
+                let (len, mut values) = self.len(12)?;
+                for _ in 0..len {
+                    values.push(self.vector3()?);
+                }
+                Value::PoolVector3Array(values)
+            }
+            TYPE_POOL_COLOR_ARRAY => {
+                let (len, mut values) = self.len(16)?;
+                for _ in 0..len {
+                    values.push(self.color()?);
+                }
+                Value::PoolColorArray(values)
+            }
+            type_id => return Err(DecodeError::UnsupportedType { offset, type_id }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gdnative::core_types::Vector2;
+
+    use crate::{
+        collections::{HashMap, HashSet},
+        marshal::{decode, decode_value, encode, encode_value, DecodeError},
+        value::{ResourceError, ResourceRef, Value},
+        vec::Vec,
+    };
+
+    #[test]
+    fn test_int() {
+        assert_eq!(encode(&7).unwrap(), [2, 0, 0, 0, 7, 0, 0, 0]);
+        assert_eq!(encode(&-1).unwrap(), [2, 0, 0, 0, 255, 255, 255, 255]);
+        assert_eq!(
+            encode(&(1i64 << 40)).unwrap(),
+            [2, 0, 1, 0, 0, 0, 0, 0, 0, 1, 0, 0]
+        );
+        assert_eq!(
+            decode(&[2, 0, 1, 0, 0, 0, 0, 0, 0, 1, 0, 0]),
+            Ok(1i64 << 40)
+        );
+    }
+
+    #[test]
+    fn test_real() {
+        assert_eq!(encode(&0.5).unwrap(), [3, 0, 0, 0, 0, 0, 0, 63]);
+        assert_eq!(
+            encode(&0.1).unwrap(),
+            [3, 0, 1, 0, 154, 153, 153, 153, 153, 153, 185, 63]
+        );
+        assert_eq!(
+            decode(&[3, 0, 1, 0, 154, 153, 153, 153, 153, 153, 185, 63]),
+            Ok(0.1)
+        );
+    }
+
+    #[test]
+    fn test_string() {
+        assert_eq!(
+            encode("abc").unwrap(),
+            [4, 0, 0, 0, 3, 0, 0, 0, 97, 98, 99, 0]
+        );
+        assert_eq!(
+            decode(&[4, 0, 0, 0, 3, 0, 0, 0, 97, 98, 99, 0]),
+            Ok("abc".to_string())
+        );
+    }
+
+    #[test]
+    fn test_vec() {
+        let bytes = [
+            19, 0, 0, 0, 2, 0, 0, 0, 4, 0, 0, 0, 1, 0, 0, 0, 97, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        let vec = Vec::from(vec![Some("a".to_string()), None]);
+
+        assert_eq!(encode(&vec).unwrap(), bytes);
+        assert_eq!(decode(&bytes), Ok(vec));
+    }
+
+    #[test]
+    fn test_hash_map() {
+        let bytes = [
+            18, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0, 1, 0, 0, 0, 97, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0,
+        ];
+        let map = HashMap::from([("a".to_string(), 1)]);
+
+        assert_eq!(encode(&map).unwrap(), bytes);
+        assert_eq!(decode(&bytes), Ok(map));
+    }
+
+    #[test]
+    fn test_hash_set_from_pool_array() {
+        let bytes = [21, 0, 0, 0, 2, 0, 0, 0, 5, 0, 0, 0, 6, 0, 0, 0];
+
+        assert_eq!(decode(&bytes), Ok(HashSet::from([5, 6])));
+    }
+
+    #[test]
+    fn test_pool_arrays() {
+        let bytes = [20, 0, 0, 0, 3, 0, 0, 0, 1, 2, 3, 0];
+        let value = Value::PoolByteArray(vec![1, 2, 3]);
+        assert_eq!(encode_value(&value).unwrap(), bytes);
+        assert_eq!(decode_value(&bytes), Ok(value));
+
+        let bytes = [23, 0, 0, 0, 1, 0, 0, 0, 3, 0, 0, 0, 97, 98, 0, 0];
+        let value = Value::PoolStringArray(vec!["ab".to_string()]);
+        assert_eq!(encode_value(&value).unwrap(), bytes);
+        assert_eq!(decode_value(&bytes), Ok(value));
+
+        let bytes = [24, 0, 0, 0, 1, 0, 0, 0, 0, 0, 128, 63, 0, 0, 0, 64];
+        let value = Value::PoolVector2Array(vec![Vector2::new(1.0, 2.0)]);
+        assert_eq!(encode_value(&value).unwrap(), bytes);
+        assert_eq!(decode_value(&bytes), Ok(value));
+    }
+
+    #[test]
+    fn test_node_path() {
+        let bytes = [
+            15, 0, 0, 0, 2, 0, 0, 128, 1, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0, 114, 111, 111, 116, 1,
+            0, 0, 0, 97, 0, 0, 0, 1, 0, 0, 0, 98, 0, 0, 0,
+        ];
+        let value = Value::NodePath("/root/a:b".to_string());
+
+        assert_eq!(encode_value(&value).unwrap(), bytes);
+        assert_eq!(decode_value(&bytes), Ok(value));
+    }
+
+    #[test]
+    fn test_resource() {
+        let resource = ResourceRef::External(1);
+        let value = Value::Array(vec![Value::Int(1), Value::Resource(resource.clone())]);
+
+        assert_eq!(encode_value(&value), Err(ResourceError { resource }));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            decode_value(&[2, 0, 0, 0, 7]),
+            Err(DecodeError::UnexpectedEnd { offset: 5 })
+        );
+        assert_eq!(
+            decode_value(&[17, 0, 0, 0]),
+            Err(DecodeError::UnsupportedType {
+                offset: 0,
+                type_id: 17
+            })
+        );
+        assert_eq!(
+            decode_value(&[0, 0, 0, 0, 0]),
+            Err(DecodeError::TrailingBytes { offset: 4 })
+        );
+        assert!(matches!(
+            decode::<Vec<i32>>(&[0, 0, 0, 0]),
+            Err(DecodeError::Value(_))
+        ));
+    }
+
+    #[test]
+    fn test_too_deep() {
+        let nested = |depth: usize| {
+            let mut bytes = [19, 0, 0, 0, 1, 0, 0, 0].repeat(depth);
+            bytes.extend_from_slice(&[0, 0, 0, 0]);
+            bytes
+        };
+
+        assert!(decode_value(&nested(512)).is_ok());
+        assert_eq!(
+            decode_value(&nested(513)),
+            Err(DecodeError::TooDeep { offset: 4096 })
+        );
+        assert_eq!(
+            decode_value(&nested(300_000)),
+            Err(DecodeError::TooDeep { offset: 4096 })
+        );
+    }
+}
@@ -0,0 +1,385 @@
+//! Helpers for exposing the keys of a map as individual properties.
+
+use std::hash::BuildHasher;
+
+use gdnative::{
+    core_types::{Dictionary, VariantArray, VariantType},
+    prelude::{FromVariant, OwnedToVariant, ToVariant, Variant},
+};
+
+use crate::collections::HashMap;
+
+/// `PROPERTY_USAGE_STORAGE | PROPERTY_USAGE_EDITOR | PROPERTY_USAGE_NETWORK`.
+const PROPERTY_USAGE_DEFAULT: i64 = 7;
+/// `PROPERTY_USAGE_GROUP`.
+const PROPERTY_USAGE_GROUP: i64 = 128;
+
+/// Exposes each key of a `HashMap<String, V>` as its own property.
+///
+/// Call [`property_list`](Self::property_list), [`get`](Self::get) and
+/// [`set`](Self::set) from the `_get_property_list`, `_get` and `_set`
+/// methods of a `NativeClass`. The properties are listed in key order.
+///
+/// Property names are the keys prefixed with the group prefix, if any. Every
+/// property has the `VariantType` passed to [`new`](Self::new) and the
+/// [`PropertyHint`] set with [`hint`](Self::hint), whatever its current value.
+///
+/// # Limitations
+///
+/// - The type and hint are not read from `V`'s `Export` impl, since the
+///   `ExportInfo` it returns cannot be inspected. They must match `V`.
+/// - [`set`](Self::set) only replaces the values of existing keys. Godot
+///   cannot add a key through `_set`; insert it into the map instead.
+///
+/// # Examples
+///
+/// ```ignore
+/// use gdnative::prelude::*;
+/// use gdvariants::{
+///     collections::HashMap,
+///     property::{MapProperties, PropertyHint},
+/// };
+///
+/// #[derive(NativeClass)]
+/// #[inherit(Node)]
+/// #[no_constructor]
+/// pub struct Stats {
+///     stats: HashMap<String, i64>,
+///     properties: MapProperties,
+/// }
+///
+/// #[methods]
+/// impl Stats {
+///     fn new(_owner: &Node) -> Stats {
+///         Stats {
+///             stats: HashMap::new(),
+///             properties: MapProperties::new(VariantType::I64).hint(PropertyHint::Range {
+///                 min: 0.0,
+///                 max: 99.0,
+///                 step: 1.0,
+///             }),
+///         }
+///     }
+///
+///     #[export]
+///     fn _get_property_list(&self, _owner: &Node) -> VariantArray {
+///         self.properties.property_list(&self.stats)
+///     }
+///
+///     #[export]
+///     fn _get(&self, _owner: &Node, property: String) -> Variant {
+///         self.properties.get(&self.stats, &property).unwrap_or_default()
+///     }
+///
+///     #[export]
+///     fn _set(&mut self, _owner: &Node, property: String, value: Variant) -> bool {
+///         self.properties.set(&mut self.stats, &property, &value)
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct MapProperties {
+    group: Option<(String, String)>,
+    variant_type: VariantType,
+    hint: PropertyHint,
+}
+
+impl MapProperties {
+    /// Creates a helper that lists the keys as top-level properties of type
+    /// `variant_type`.
+    #[inline]
+    pub fn new(variant_type: VariantType) -> MapProperties {
+        MapProperties {
+            group: None,
+            variant_type,
+            hint: PropertyHint::None,
+        }
+    }
+
+    /// Creates a helper that lists the keys under the property group `name`.
+    ///
+    /// Godot puts every property starting with `prefix` into the group, so
+    /// the property names are the keys with `prefix` prepended.
+    #[inline]
+    pub fn with_group(
+        variant_type: VariantType,
+        name: impl Into<String>,
+        prefix: impl Into<String>,
+    ) -> MapProperties {
+        MapProperties {
+            group: Some((name.into(), prefix.into())),
+            ..MapProperties::new(variant_type)
+        }
+    }
+
+    /// Sets the hint shared by all properties.
+    #[inline]
+    pub fn hint(mut self, hint: PropertyHint) -> MapProperties {
+        self.hint = hint;
+        self
+    }
+
+    /// Returns the property name of `key`.
+    pub fn property_name(&self, key: &str) -> String {
+        match &self.group {
+            Some((_, prefix)) => format!("{}{}", prefix, key),
+            None => key.to_string(),
+        }
+    }
+
+    /// Returns the key of the property `property`, or `None` if it does not
+    /// belong to the map.
+    pub fn key<'a>(&self, property: &'a str) -> Option<&'a str> {
+        match &self.group {
+            Some((_, prefix)) => property.strip_prefix(prefix.as_str()),
+            None => Some(property),
+        }
+    }
+
+    /// Returns the property list, for `_get_property_list`.
+    pub fn property_list<V, S>(&self, map: &HashMap<String, V, S>) -> VariantArray {
+        let list = VariantArray::new();
+        if let Some((name, prefix)) = &self.group {
+            list.push(property(
+                name,
+                VariantType::Nil,
+                0,
+                prefix,
+                PROPERTY_USAGE_GROUP,
+            ));
+        }
+        let mut keys: std::vec::Vec<&String> = map.keys().collect();
+        keys.sort();
+        let hint_string = self.hint.hint_string();
+        for key in keys {
+            list.push(property(
+                &self.property_name(key),
+                self.variant_type,
+                self.hint.kind(),
+                &hint_string,
+                PROPERTY_USAGE_DEFAULT,
+            ));
+        }
+        list.into_shared()
+    }
+
+    /// Returns the value of the property `property`, for `_get`.
+    ///
+    /// Returns `None` if the property does not belong to the map.
+    pub fn get<V, S>(&self, map: &HashMap<String, V, S>, property: &str) -> Option<Variant>
+    where
+        V: ToVariant,
+        S: BuildHasher,
+    {
+        map.get(self.key(property)?).map(ToVariant::to_variant)
+    }
+
+    /// Sets the value of the property `property`, for `_set`.
+    ///
+    /// Returns `false` if the property does not belong to the map or the
+    /// value cannot be converted into `V`, in which case the map is left
+    /// unchanged.
+    pub fn set<V, S>(
+        &self,
+        map: &mut HashMap<String, V, S>,
+        property: &str,
+        value: &Variant,
+    ) -> bool
+    where
+        V: FromVariant,
+        S: BuildHasher,
+    {
+        self.replace(map, property, || V::from_variant(value).ok())
+    }
+
+    fn replace<V, S>(
+        &self,
+        map: &mut HashMap<String, V, S>,
+        property: &str,
+        value: impl FnOnce() -> Option<V>,
+    ) -> bool
+    where
+        S: BuildHasher,
+    {
+        match self.key(property).and_then(|key| map.get_mut(key)) {
+            Some(slot) => match value() {
+                Some(value) => {
+                    *slot = value;
+                    true
+                }
+                None => false,
+            },
+            None => false,
+        }
+    }
+}
+
+/// A Godot 3 `PropertyHint` with its hint string.
+///
+/// Covers the hints that apply to plain values. gdnative's own hint types
+/// only produce an opaque `ExportInfo`, so they cannot be reused here.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyHint {
+    /// No hint.
+    None,
+    /// A number between `min` and `max`, in increments of `step`.
+    Range { min: f64, max: f64, step: f64 },
+    /// Like `Range`, but edited on an exponential scale.
+    ExpRange { min: f64, max: f64, step: f64 },
+    /// An integer or string chosen from a list of names.
+    Enum(std::vec::Vec<String>),
+    /// An integer bitmask with one named flag per bit.
+    Flags(std::vec::Vec<String>),
+    /// A path to a file matching the filters, such as `"*.png"`.
+    File(std::vec::Vec<String>),
+    /// A path to a directory.
+    Dir,
+    /// A string edited in a multiline text box.
+    MultilineText,
+    /// A string with placeholder text shown while it is empty.
+    PlaceholderText(String),
+    /// A color edited without its alpha channel.
+    ColorNoAlpha,
+}
+
+impl PropertyHint {
+    /// Returns the value of the `PropertyHint` enum in Godot.
+    pub fn kind(&self) -> i64 {
+        match self {
+            PropertyHint::None => 0,
+            PropertyHint::Range { .. } => 1,
+            PropertyHint::ExpRange { .. } => 2,
+            PropertyHint::Enum(_) => 3,
+            PropertyHint::Flags(_) => 8,
+            PropertyHint::File(_) => 13,
+            PropertyHint::Dir => 14,
+            PropertyHint::MultilineText => 18,
+            PropertyHint::PlaceholderText(_) => 19,
+            PropertyHint::ColorNoAlpha => 20,
+        }
+    }
+
+    /// Returns the hint string Godot expects for the hint.
+    pub fn hint_string(&self) -> String {
+        match self {
+            PropertyHint::Range { min, max, step } | PropertyHint::ExpRange { min, max, step } => {
+                format!("{},{},{}", min, max, step)
+            }
+            PropertyHint::Enum(names) | PropertyHint::Flags(names) | PropertyHint::File(names) => {
+                names.join(",")
+            }
+            PropertyHint::PlaceholderText(text) => text.clone(),
+            PropertyHint::None
+            | PropertyHint::Dir
+            | PropertyHint::MultilineText
+            | PropertyHint::ColorNoAlpha => String::new(),
+        }
+    }
+}
+
+fn property(
+    name: &str,
+    variant_type: VariantType,
+    hint: i64,
+    hint_string: &str,
+    usage: i64,
+) -> Variant {
+    let dictionary = Dictionary::new();
+    dictionary.insert("name", name);
+    dictionary.insert("type", variant_type as i64);
+    dictionary.insert("hint", hint);
+    dictionary.insert("hint_string", hint_string);
+    dictionary.insert("usage", usage);
+    dictionary.owned_to_variant()
+}
+
+#[cfg(test)]
+mod tests {
+    use gdnative::{
+        core_types::VariantType,
+        prelude::{ToVariant, Variant},
+    };
+
+    use crate::{
+        collections::HashMap,
+        property::{MapProperties, PropertyHint},
+    };
+
+    fn stats() -> HashMap<String, i64> {
+        HashMap::from([("strength".to_string(), 3)])
+    }
+
+    #[test]
+    fn test_property_name() {
+        let properties = MapProperties::new(VariantType::I64);
+
+        assert_eq!(properties.property_name("strength"), "strength");
+        assert_eq!(properties.key("strength"), Some("strength"));
+    }
+
+    #[test]
+    fn test_property_name_group() {
+        let properties = MapProperties::with_group(VariantType::I64, "Stats", "stats_");
+
+        assert_eq!(properties.property_name("strength"), "stats_strength");
+        assert_eq!(properties.key("stats_strength"), Some("strength"));
+        assert_eq!(properties.key("strength"), None);
+    }
+
+    #[test]
+    fn test_replace() {
+        let properties = MapProperties::with_group(VariantType::I64, "Stats", "stats_");
+        let mut stats = stats();
+
+        assert!(properties.replace(&mut stats, "stats_strength", || Some(5)));
+        assert_eq!(stats, HashMap::from([("strength".to_string(), 5)]));
+    }
+
+    #[test]
+    fn test_replace_rejected() {
+        let properties = MapProperties::with_group(VariantType::I64, "Stats", "stats_");
+        let mut stats = stats();
+
+        // Keys outside the group, missing keys and failed conversions are
+        // all rejected without touching the map.
+        assert!(!properties.replace(&mut stats, "strength", || Some(5)));
+        assert!(!properties.replace(&mut stats, "stats_agility", || Some(5)));
+        assert!(!properties.replace(&mut stats, "stats_strength", || None));
+        assert_eq!(stats, self::stats());
+    }
+
+    #[test]
+    fn test_hint() {
+        let range = PropertyHint::Range {
+            min: 0.0,
+            max: 99.0,
+            step: 0.5,
+        };
+        let flags = PropertyHint::Flags(vec!["Fire".to_string(), "Water".to_string()]);
+
+        assert_eq!(
+            (range.kind(), range.hint_string()),
+            (1, "0,99,0.5".to_string())
+        );
+        assert_eq!(
+            (flags.kind(), flags.hint_string()),
+            (8, "Fire,Water".to_string())
+        );
+        assert_eq!(
+            (PropertyHint::None.kind(), PropertyHint::None.hint_string()),
+            (0, String::new())
+        );
+    }
+
+    #[test]
+    #[ignore = "creating a Variant needs the Godot engine"]
+    fn test_set() {
+        let properties = MapProperties::new(VariantType::I64);
+        let mut stats = stats();
+
+        assert!(properties.set(&mut stats, "strength", &5.to_variant()));
+        assert!(!properties.set(&mut stats, "strength", &"five".to_variant()));
+        assert!(!properties.set(&mut stats, "agility", &Variant::nil()));
+        assert_eq!(stats, HashMap::from([("strength".to_string(), 5)]));
+    }
+}
@@ -0,0 +1,14 @@
+//! Helpers shared by the tests of several modules.
+
+/// A xorshift generator, so the randomized tests are reproducible.
+pub(crate) struct Rng(pub(crate) u64);
+
+impl Rng {
+    /// Returns the next number below `bound`.
+    pub(crate) fn next(&mut self, bound: u64) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 % bound
+    }
+}
@@ -0,0 +1,83 @@
+//! Godot 3's text `Variant` format, as produced by `var2str` and read by
+//! `str2var`.
+//!
+//! Works on [`Value`]s, so level data and debug dumps written by GDScript can
+//! be read and written by tools that run without the engine.
+//!
+//! ```
+//! use gdvariants::{collections::HashMap, text, vec::Vec};
+//!
+//! let map: HashMap<i64, Vec<i64>> = text::parse("{ 1: [ 2, 3 ] }").unwrap();
+//! assert_eq!(map[&1], [2, 3]);
+//!
+//! assert_eq!(text::write(&map), "{\n1: [ 2, 3 ]\n}");
+//! ```
+//!
+//! Like `var2str`, dictionaries are written with their keys sorted, and
+//! floats are written with six significant digits.
+//!
+//! Property values in `.tres` and `.tscn` files use the same syntax, with
+//! [`ResourceRef`](crate::value::ResourceRef) for `ExtResource( 1 )` and
+//! `SubResource( 2 )`:
+//!
+//! ```
+//! use gdvariants::{text, value::ResourceRef, vec::Vec};
+//!
+//! let frames = Vec::from(vec![ResourceRef::External(1), ResourceRef::External(2)]);
+//! assert_eq!(text::write(&frames), "[ ExtResource( 1 ), ExtResource( 2 ) ]");
+//! ```
+
+mod parse;
+mod write;
+
+use std::{error::Error, fmt};
+
+pub use parse::parse_value;
+pub(crate) use parse::Parser;
+pub use write::write_value;
+
+use crate::value::{FromValue, FromValueError, ToValue};
+
+/// Writes `value` the way `var2str` does.
+pub fn write<T>(value: &T) -> String
+where
+    T: ToValue + ?Sized,
+{
+    write_value(&value.to_value())
+}
+
+/// Parses a `T` from the output of `var2str`.
+pub fn parse<T>(text: &str) -> Result<T, ParseError>
+where
+    T: FromValue,
+{
+    T::from_value(&parse_value(text)?).map_err(ParseError::Value)
+}
+
+/// Error returned by [`parse`] and [`parse_value`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The text is not valid. Lines and columns start at 1.
+    Syntax {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// The value could not be converted into the requested type.
+    Value(FromValueError),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Syntax {
+                line,
+                column,
+                message,
+            } => write!(f, "line {}, column {}: {}", line, column, message),
+            ParseError::Value(error) => error.fmt(f),
+        }
+    }
+}
+
+impl Error for ParseError {}
@@ -0,0 +1,666 @@
+use gdnative::core_types::{
+    Aabb, Basis, Color, Plane, Quat, Rect2, Transform, Transform2D, Vector2, Vector3,
+};
+
+use crate::{
+    text::ParseError,
+    value::{ResourceRef, Value},
+};
+
+/// Parses the output of `var2str`.
+///
+/// Fails if the text holds anything but a single value, apart from
+/// whitespace and `;` comments, or if arrays, dictionaries and constructors
+/// are nested more than 512 deep.
+pub fn parse_value(text: &str) -> Result<Value, ParseError> {
+    let mut parser = Parser::new(text);
+    let value = parser.value()?;
+    parser.end()?;
+    Ok(value)
+}
+
+/// Constructors `str2var` understands, including the Godot 2 names it still
+/// accepts, and the resource references of `.tres` and `.tscn` files.
+const CONSTRUCTORS: &[&str] = &[
+    "Vector2",
+    "Rect2",
+    "Vector3",
+    "Transform2D",
+    "Matrix32",
+    "Plane",
+    "Quat",
+    "AABB",
+    "Rect3",
+    "Basis",
+    "Matrix3",
+    "Transform",
+    "Color",
+    "NodePath",
+    "PoolByteArray",
+    "ByteArray",
+    "PoolIntArray",
+    "IntArray",
+    "PoolRealArray",
+    "RealArray",
+    "FloatArray",
+    "PoolStringArray",
+    "StringArray",
+    "PoolVector2Array",
+    "Vector2Array",
+    "PoolVector3Array",
+    "Vector3Array",
+    "PoolColorArray",
+    "ColorArray",
+    "ExtResource",
+    "SubResource",
+    "Resource",
+];
+
+/// How deep arrays, dictionaries and constructors may be nested.
+const MAX_DEPTH: usize = 512;
+
+pub(crate) struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+    /// How many arrays, dictionaries and constructors contain the current
+    /// value.
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    pub(crate) fn new(text: &'a str) -> Parser<'a> {
+        Parser::at(text, 0)
+    }
+
+    /// Creates a parser that starts at byte `pos` of `text`, so errors report
+    /// positions in the whole text.
+    pub(crate) fn at(text: &'a str, pos: usize) -> Parser<'a> {
+        Parser {
+            text,
+            pos,
+            depth: 0,
+        }
+    }
+
+    pub(crate) fn position(&self) -> usize {
+        self.pos
+    }
+
+    pub(crate) fn error_at(&self, pos: usize, message: impl Into<String>) -> ParseError {
+        let before = &self.text[..pos];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        ParseError::Syntax {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn error(&self, message: impl Into<String>) -> ParseError {
+        self.error_at(self.pos, message)
+    }
+
+    pub(crate) fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    /// Skips whitespace and `;` comments.
+    pub(crate) fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if c == ';' {
+                while !matches!(self.bump(), None | Some('\n')) {}
+            } else if c.is_whitespace() {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Skips whitespace and consumes `expected`.
+    pub(crate) fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.bump();
+                Ok(())
+            }
+            Some(c) => Err(self.error(format!("expected '{}', found '{}'", expected, c))),
+            None => Err(self.error(format!("expected '{}', found end of text", expected))),
+        }
+    }
+
+    /// Fails unless only whitespace and comments are left.
+    pub(crate) fn end(&mut self) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(c) => Err(self.error(format!("unexpected '{}' after the value", c))),
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) fn identifier(&mut self) -> &'a str {
+        let start = self.pos;
+        while matches!(self.peek(), Some(c) if c.is_alphanumeric() || c == '_') {
+            self.bump();
+        }
+        &self.text[start..self.pos]
+    }
+
+    /// Steps into an array, dictionary or constructor starting at byte
+    /// `start`.
+    fn enter(&mut self, start: usize) -> Result<(), ParseError> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error_at(start, "values nested too deeply"));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Parses a list of values separated by `,` up to `close`, after the
+    /// opening bracket has been consumed.
+    fn list(&mut self, close: char) -> Result<std::vec::Vec<Value>, ParseError> {
+        let mut values = std::vec::Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(close) {
+                self.bump();
+                return Ok(values);
+            }
+            values.push(self.value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => {
+                    self.bump();
+                }
+                Some(c) if c == close => {}
+                Some(c) => {
+                    return Err(self.error(format!("expected ',' or '{}', found '{}'", close, c)))
+                }
+                None => {
+                    return Err(
+                        self.error(format!("expected ',' or '{}', found end of text", close))
+                    )
+                }
+            }
+        }
+    }
+
+    fn dictionary(&mut self) -> Result<Value, ParseError> {
+        let mut entries = std::vec::Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some('}') {
+                self.bump();
+                return Ok(Value::Dictionary(entries));
+            }
+            let key = self.value()?;
+            self.expect(':')?;
+            let value = self.value()?;
+            entries.push((key, value));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => {
+                    self.bump();
+                }
+                Some('}') => {}
+                Some(c) => return Err(self.error(format!("expected ',' or '}}', found '{}'", c))),
+                None => return Err(self.error("expected ',' or '}', found end of text")),
+            }
+        }
+    }
+
+    pub(crate) fn string(&mut self) -> Result<String, ParseError> {
+        let start = self.pos;
+        self.bump();
+        let mut string = String::new();
+        loop {
+            match self.bump() {
+                Some('"') => return Ok(string),
+                Some('\\') => {
+                    let escape = self.pos - 1;
+                    string.push(match self.bump() {
+                        Some('b') => '\u{8}',
+                        Some('t') => '\t',
+                        Some('n') => '\n',
+                        Some('f') => '\u{c}',
+                        Some('r') => '\r',
+                        Some('u') => {
+                            let hex = self.text.get(self.pos..self.pos + 4).unwrap_or("");
+                            let c = u32::from_str_radix(hex, 16)
+                                .ok()
+                                .and_then(char::from_u32)
+                                .ok_or_else(|| self.error_at(escape, "invalid unicode escape"))?;
+                            self.pos += 4;
+                            c
+                        }
+                        Some(c @ ('"' | '\\' | '\'' | '/')) => c,
+                        _ => return Err(self.error_at(escape, "invalid escape sequence")),
+                    });
+                }
+                Some(c) => string.push(c),
+                None => return Err(self.error_at(start, "unterminated string")),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, ParseError> {
+        let start = self.pos;
+        if self.peek() == Some('-') {
+            self.bump();
+        }
+        if self.text[self.pos..].starts_with("inf") {
+            self.pos += 3;
+            return Ok(Value::Real(f64::NEG_INFINITY));
+        }
+        let mut real = false;
+        while let Some(c) = self.peek() {
+            match c {
+                '0'..='9' => {}
+                '.' | 'e' | 'E' => real = true,
+                '+' | '-' if matches!(self.text[..self.pos].chars().last(), Some('e' | 'E')) => {}
+                _ => break,
+            }
+            self.bump();
+        }
+        let number = &self.text[start..self.pos];
+        let value = if real {
+            number.parse().ok().map(Value::Real)
+        } else {
+            number.parse().ok().map(Value::Int)
+        };
+        value.ok_or_else(|| self.error_at(start, format!("invalid number '{}'", number)))
+    }
+
+    pub(crate) fn value(&mut self) -> Result<Value, ParseError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.peek() {
+            Some('{') => {
+                self.enter(start)?;
+                self.bump();
+                let value = self.dictionary();
+                self.depth -= 1;
+                value
+            }
+            Some('[') => {
+                self.enter(start)?;
+                self.bump();
+                let values = self.list(']');
+                self.depth -= 1;
+                values.map(Value::Array)
+            }
+            Some('"') => self.string().map(Value::String),
+            Some('-' | '.' | '0'..='9') => self.number(),
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                let identifier = self.identifier();
+                match identifier {
+                    "null" | "nil" => Ok(Value::Nil),
+                    "true" => Ok(Value::Bool(true)),
+                    "false" => Ok(Value::Bool(false)),
+                    "inf" => Ok(Value::Real(f64::INFINITY)),
+                    "nan" => Ok(Value::Real(f64::NAN)),
+                    _ => self.constructor(start, identifier),
+                }
+            }
+            Some(c) => Err(self.error(format!("unexpected '{}'", c))),
+            None => Err(self.error("unexpected end of text")),
+        }
+    }
+
+    fn constructor(&mut self, start: usize, name: &str) -> Result<Value, ParseError> {
+        if !CONSTRUCTORS.contains(&name) {
+            return Err(self.error_at(start, format!("unknown identifier '{}'", name)));
+        }
+        self.expect('(')?;
+        self.enter(start)?;
+        let args = self.list(')');
+        self.depth -= 1;
+        self.construct(start, name, args?)
+    }
+
+    /// Builds the value of a constructor from its arguments.
+    ///
+    /// Kept out of `constructor` so the frames of nested values stay small.
+    #[inline(never)]
+    fn construct(
+        &self,
+        start: usize,
+        name: &str,
+        args: std::vec::Vec<Value>,
+    ) -> Result<Value, ParseError> {
+        let arguments = Arguments {
+            parser: self,
+            start,
+            name,
+            args,
+        };
+        Ok(match name {
+            "Vector2" => Value::Vector2(vector2(arguments.reals()?)),
+            "Rect2" => {
+                let [x, y, w, h] = arguments.reals()?;
+                Value::Rect2(Rect2 {
+                    position: Vector2::new(x, y),
+                    size: Vector2::new(w, h),
+                })
+            }
+            "Vector3" => Value::Vector3(vector3(arguments.reals()?)),
+            "Transform2D" | "Matrix32" => {
+                let [ax, ay, bx, by, ox, oy] = arguments.reals()?;
+                Value::Transform2D(Transform2D {
+                    a: Vector2::new(ax, ay),
+                    b: Vector2::new(bx, by),
+                    origin: Vector2::new(ox, oy),
+                })
+            }
+            "Plane" => {
+                let [x, y, z, d] = arguments.reals()?;
+                Value::Plane(Plane {
+                    normal: Vector3::new(x, y, z),
+                    d,
+                })
+            }
+            "Quat" => {
+                let [x, y, z, w] = arguments.reals()?;
+                Value::Quat(Quat::new(x, y, z, w))
+            }
+            "AABB" | "Rect3" => {
+                let [x, y, z, w, h, d] = arguments.reals()?;
+                Value::Aabb(Aabb {
+                    position: Vector3::new(x, y, z),
+                    size: Vector3::new(w, h, d),
+                })
+            }
+            "Basis" | "Matrix3" => Value::Basis(basis(&arguments.reals::<9>()?)),
+            "Transform" => {
+                let reals = arguments.reals::<12>()?;
+                Value::Transform(Transform {
+                    basis: basis(&reals[..9]),
+                    origin: Vector3::new(reals[9], reals[10], reals[11]),
+                })
+            }
+            "Color" => match arguments.args.len() {
+                3 => {
+                    let [r, g, b] = arguments.reals()?;
+                    Value::Color(Color::from_rgba(r, g, b, 1.0))
+                }
+                _ => Value::Color(color(arguments.reals()?)),
+            },
+            "NodePath" => Value::NodePath(arguments.string()?),
+            "ExtResource" => Value::Resource(ResourceRef::External(arguments.id()?)),
+            "SubResource" => Value::Resource(ResourceRef::Sub(arguments.id()?)),
+            "Resource" => Value::Resource(ResourceRef::Path(arguments.string()?)),
+            "PoolByteArray" | "ByteArray" => Value::PoolByteArray(arguments.ints()?),
+            "PoolIntArray" | "IntArray" => Value::PoolIntArray(arguments.ints()?),
+            "PoolRealArray" | "RealArray" | "FloatArray" => {
+                Value::PoolRealArray(arguments.real_list(1)?)
+            }
+            "PoolStringArray" | "StringArray" => Value::PoolStringArray(arguments.strings()?),
+            "PoolVector2Array" | "Vector2Array" => Value::PoolVector2Array(
+                arguments
+                    .real_list(2)?
+                    .chunks(2)
+                    .map(|chunk| Vector2::new(chunk[0], chunk[1]))
+                    .collect(),
+            ),
+            "PoolVector3Array" | "Vector3Array" => Value::PoolVector3Array(
+                arguments
+                    .real_list(3)?
+                    .chunks(3)
+                    .map(|chunk| Vector3::new(chunk[0], chunk[1], chunk[2]))
+                    .collect(),
+            ),
+            _ => Value::PoolColorArray(
+                arguments
+                    .real_list(4)?
+                    .chunks(4)
+                    .map(|chunk| Color::from_rgba(chunk[0], chunk[1], chunk[2], chunk[3]))
+                    .collect(),
+            ),
+        })
+    }
+}
+
+fn vector2([x, y]: [f32; 2]) -> Vector2 {
+    Vector2::new(x, y)
+}
+
+fn vector3([x, y, z]: [f32; 3]) -> Vector3 {
+    Vector3::new(x, y, z)
+}
+
+fn color([r, g, b, a]: [f32; 4]) -> Color {
+    Color::from_rgba(r, g, b, a)
+}
+
+fn basis(reals: &[f32]) -> Basis {
+    Basis {
+        elements: [
+            Vector3::new(reals[0], reals[1], reals[2]),
+            Vector3::new(reals[3], reals[4], reals[5]),
+            Vector3::new(reals[6], reals[7], reals[8]),
+        ],
+    }
+}
+
+/// The arguments of a constructor such as `Vector2( 1, 2 )`.
+struct Arguments<'p, 'a> {
+    parser: &'p Parser<'a>,
+    start: usize,
+    name: &'p str,
+    args: std::vec::Vec<Value>,
+}
+
+impl Arguments<'_, '_> {
+    fn error(&self, expected: &str) -> ParseError {
+        self.parser
+            .error_at(self.start, format!("{} expects {}", self.name, expected))
+    }
+
+    fn real_list(&self, group: usize) -> Result<std::vec::Vec<f32>, ParseError> {
+        if self.args.len() % group != 0 {
+            return Err(self.error(&format!("a multiple of {} numbers", group)));
+        }
+        self.args
+            .iter()
+            .map(|arg| arg.as_real().map(|real| real as f32))
+            .collect::<Option<_>>()
+            .ok_or_else(|| self.error("numbers"))
+    }
+
+    fn reals<const N: usize>(&self) -> Result<[f32; N], ParseError> {
+        let reals = self.real_list(1)?;
+        reals
+            .try_into()
+            .map_err(|_| self.error(&format!("{} numbers", N)))
+    }
+
+    fn ints<T: TryFrom<i64>>(&self) -> Result<std::vec::Vec<T>, ParseError> {
+        self.args
+            .iter()
+            .map(|arg| arg.as_int().and_then(|int| T::try_from(int).ok()))
+            .collect::<Option<_>>()
+            .ok_or_else(|| self.error("integers in range"))
+    }
+
+    fn strings(&self) -> Result<std::vec::Vec<String>, ParseError> {
+        self.args
+            .iter()
+            .map(|arg| arg.as_str().map(str::to_string))
+            .collect::<Option<_>>()
+            .ok_or_else(|| self.error("strings"))
+    }
+
+    fn string(&self) -> Result<String, ParseError> {
+        match self.args.as_slice() {
+            [Value::String(string)] => Ok(string.clone()),
+            _ => Err(self.error("a string")),
+        }
+    }
+
+    fn id(&self) -> Result<u32, ParseError> {
+        match self.ints()?.as_slice() {
+            [id] => Ok(*id),
+            _ => Err(self.error("an id")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gdnative::core_types::{Color, Vector2};
+
+    use crate::{
+        collections::{HashMap, HashSet},
+        text::{parse, parse_value, write, write_value, ParseError},
+        value::{ResourceRef, Value},
+        vec::Vec,
+    };
+
+    fn syntax(line: usize, column: usize, message: &str) -> ParseError {
+        ParseError::Syntax {
+            line,
+            column,
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_primitives() {
+        assert_eq!(parse_value("null"), Ok(Value::Nil));
+        assert_eq!(parse("true"), Ok(true));
+        assert_eq!(parse("-3"), Ok(-3));
+        assert_eq!(parse("1.5e+06"), Ok(1_500_000.0));
+        assert_eq!(parse("\"a\\\"b\\n\\u00e9\""), Ok("a\"b\né".to_string()));
+    }
+
+    #[test]
+    fn test_request_example() {
+        let value = parse_value("{ 1: [ 2, 3 ], \"a\": Vector2( 1, 2 ) }").unwrap();
+
+        assert_eq!(
+            value,
+            Value::Dictionary(vec![
+                (
+                    Value::Int(1),
+                    Value::Array(vec![Value::Int(2), Value::Int(3)])
+                ),
+                (
+                    Value::String("a".to_string()),
+                    Value::Vector2(Vector2::new(1.0, 2.0))
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_constructors() {
+        assert_eq!(
+            parse("Color( 1, 0.5, 0, 1 )"),
+            Ok(Color::from_rgba(1.0, 0.5, 0.0, 1.0))
+        );
+        assert_eq!(
+            parse_value("NodePath(\"../a:b\")"),
+            Ok(Value::NodePath("../a:b".to_string()))
+        );
+        assert_eq!(
+            parse_value("PoolVector2Array(  )"),
+            Ok(Value::PoolVector2Array(vec![]))
+        );
+        assert_eq!(
+            parse::<Vec<i32>>("PoolIntArray( 1, 2 )"),
+            Ok(Vec::from(vec![1, 2]))
+        );
+    }
+
+    #[test]
+    fn test_resources() {
+        assert_eq!(
+            parse("[ ExtResource( 1 ), SubResource( 2 ) ]"),
+            Ok(Vec::from(vec![
+                ResourceRef::External(1),
+                ResourceRef::Sub(2)
+            ]))
+        );
+        assert_eq!(
+            parse("{ \"icon\": Resource( \"res://icon.png\" ) }"),
+            Ok(HashMap::from([(
+                "icon".to_string(),
+                ResourceRef::Path("res://icon.png".to_string())
+            )]))
+        );
+        assert_eq!(
+            parse_value("ExtResource( -1 )"),
+            Err(syntax(1, 1, "ExtResource expects integers in range"))
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let map = HashMap::from([
+            ("b".to_string(), HashSet::from([1])),
+            ("a".to_string(), HashSet::from([2])),
+        ]);
+        let text = write(&map);
+
+        assert_eq!(parse(&text), Ok(map));
+
+        let value = Value::PoolStringArray(vec!["x\\y".to_string(), "\"".to_string()]);
+        assert_eq!(parse_value(&write_value(&value)), Ok(value));
+    }
+
+    #[test]
+    fn test_comments() {
+        assert_eq!(parse("; header\n[ 1, ; one\n 2 ]"), Ok(vec![1, 2]));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            parse_value("{\n\"a\": [ 1, 2 }"),
+            Err(syntax(2, 13, "expected ',' or ']', found '}'"))
+        );
+        assert_eq!(
+            parse_value("[ Vector2( 1 ) ]"),
+            Err(syntax(1, 3, "Vector2 expects 2 numbers"))
+        );
+        assert_eq!(
+            parse_value("Object()"),
+            Err(syntax(1, 1, "unknown identifier 'Object'"))
+        );
+        assert_eq!(
+            parse_value("\"abc"),
+            Err(syntax(1, 1, "unterminated string"))
+        );
+        assert_eq!(
+            parse_value("1 2"),
+            Err(syntax(1, 3, "unexpected '2' after the value"))
+        );
+        assert!(matches!(parse::<i32>("\"a\""), Err(ParseError::Value(_))));
+    }
+
+    #[test]
+    fn test_too_deep() {
+        let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+
+        assert!(parse_value(&nested(512)).is_ok());
+        assert_eq!(
+            parse_value(&nested(513)),
+            Err(syntax(1, 513, "values nested too deeply"))
+        );
+        assert_eq!(
+            parse_value(&format!("\n{}", "{ 1: ".repeat(300_000))),
+            Err(syntax(2, 2561, "values nested too deeply"))
+        );
+        assert_eq!(
+            parse_value(&"Vector2( ".repeat(600)),
+            Err(syntax(1, 4609, "values nested too deeply"))
+        );
+    }
+}
@@ -0,0 +1,338 @@
+use std::{cmp::Ordering, fmt::Write};
+
+use gdnative::core_types::{Basis, Color, Vector2, Vector3};
+
+use crate::value::{ResourceRef, Value};
+
+/// Writes `value` the way `var2str` does.
+pub fn write_value(value: &Value) -> String {
+    let mut text = String::new();
+    write(&mut text, value);
+    text
+}
+
+/// Formats a number like Godot 3's `rtosfix`, which is C's `%g`: six
+/// significant digits, switching to an exponent for very small or large
+/// numbers.
+pub(crate) fn format_real(value: f64) -> String {
+    if value == 0.0 {
+        return "0".to_string();
+    }
+    if value.is_nan() {
+        return "nan".to_string();
+    }
+    if value.is_infinite() {
+        return if value > 0.0 { "inf" } else { "-inf" }.to_string();
+    }
+    let scientific = format!("{:.5e}", value);
+    let (mantissa, exponent) = scientific
+        .split_once('e')
+        .expect("exponent format always contains an exponent");
+    let exponent: i32 = exponent.parse().expect("exponent is an integer");
+    if !(-4..6).contains(&exponent) {
+        format!(
+            "{}e{}{:02}",
+            trim_zeros(mantissa),
+            if exponent < 0 { '-' } else { '+' },
+            exponent.abs()
+        )
+    } else {
+        let fixed = format!("{:.*}", (5 - exponent) as usize, value);
+        trim_zeros(&fixed).to_string()
+    }
+}
+
+fn trim_zeros(number: &str) -> &str {
+    if number.contains('.') {
+        number.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        number
+    }
+}
+
+/// Escapes `\` and `"` like Godot's `c_escape_multiline`.
+fn write_string(text: &mut String, value: &str) {
+    text.push('"');
+    for c in value.chars() {
+        if c == '\\' || c == '"' {
+            text.push('\\');
+        }
+        text.push(c);
+    }
+    text.push('"');
+}
+
+fn write_reals(text: &mut String, name: &str, values: impl IntoIterator<Item = f32>) {
+    text.push_str(name);
+    text.push_str("( ");
+    for (index, value) in values.into_iter().enumerate() {
+        if index > 0 {
+            text.push_str(", ");
+        }
+        text.push_str(&format_real(value.into()));
+    }
+    text.push_str(" )");
+}
+
+fn vector2(value: Vector2) -> [f32; 2] {
+    [value.x, value.y]
+}
+
+fn vector3(value: Vector3) -> [f32; 3] {
+    [value.x, value.y, value.z]
+}
+
+fn basis(value: Basis) -> impl Iterator<Item = f32> {
+    value.elements.into_iter().flat_map(vector3)
+}
+
+fn color(value: Color) -> [f32; 4] {
+    [value.r, value.g, value.b, value.a]
+}
+
+/// Orders dictionary keys like Godot's `Variant::operator<`: by type first,
+/// then by value for the types that can be compared.
+pub(crate) fn compare(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Value::Int(a), Value::Int(b)) => a.cmp(b),
+        (Value::Real(a), Value::Real(b)) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+        (Value::String(a), Value::String(b)) | (Value::NodePath(a), Value::NodePath(b)) => a.cmp(b),
+        _ => (a.variant_type() as u32).cmp(&(b.variant_type() as u32)),
+    }
+}
+
+fn write(text: &mut String, value: &Value) {
+    match value {
+        Value::Nil => text.push_str("null"),
+        Value::Bool(value) => text.push_str(if *value { "true" } else { "false" }),
+        Value::Int(value) => {
+            let _ = write!(text, "{}", value);
+        }
+        Value::Real(value) => {
+            let real = format_real(*value);
+            let needs_point = value.is_finite() && !real.contains(['.', 'e']);
+            text.push_str(&real);
+            if needs_point {
+                text.push_str(".0");
+            }
+        }
+        Value::String(value) => write_string(text, value),
+        Value::Vector2(value) => write_reals(text, "Vector2", vector2(*value)),
+        Value::Rect2(value) => write_reals(
+            text,
+            "Rect2",
+            vector2(value.position)
+                .into_iter()
+                .chain(vector2(value.size)),
+        ),
+        Value::Vector3(value) => write_reals(text, "Vector3", vector3(*value)),
+        Value::Transform2D(value) => write_reals(
+            text,
+            "Transform2D",
+            [value.a, value.b, value.origin]
+                .into_iter()
+                .flat_map(vector2),
+        ),
+        Value::Plane(value) => write_reals(
+            text,
+            "Plane",
+            vector3(value.normal).into_iter().chain([value.d]),
+        ),
+        Value::Quat(value) => {
+            write_reals(text, "Quat", [value.x, value.y, value.z, value.w]);
+        }
+        Value::Aabb(value) => write_reals(
+            text,
+            "AABB",
+            vector3(value.position)
+                .into_iter()
+                .chain(vector3(value.size)),
+        ),
+        Value::Basis(value) => write_reals(text, "Basis", basis(*value)),
+        Value::Transform(value) => write_reals(
+            text,
+            "Transform",
+            basis(value.basis).chain(vector3(value.origin)),
+        ),
+        Value::Color(value) => write_reals(text, "Color", color(*value)),
+        Value::NodePath(value) => {
+            text.push_str("NodePath(");
+            write_string(text, value);
+            text.push(')');
+        }
+        Value::Dictionary(entries) => {
+            let mut entries: std::vec::Vec<&(Value, Value)> = entries.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| compare(a, b));
+            text.push_str("{\n");
+            for (index, (key, value)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    text.push_str(",\n");
+                }
+                write(text, key);
+                text.push_str(": ");
+                write(text, value);
+            }
+            text.push_str("\n}");
+        }
+        Value::Array(values) => {
+            text.push_str("[ ");
+            for (index, value) in values.iter().enumerate() {
+                if index > 0 {
+                    text.push_str(", ");
+                }
+                write(text, value);
+            }
+            text.push_str(" ]");
+        }
+        Value::PoolByteArray(values) => write_ints(text, "PoolByteArray", values),
+        Value::PoolIntArray(values) => write_ints(text, "PoolIntArray", values),
+        Value::PoolRealArray(values) => {
+            write_reals(text, "PoolRealArray", values.iter().copied());
+        }
+        Value::PoolStringArray(values) => {
+            text.push_str("PoolStringArray( ");
+            for (index, value) in values.iter().enumerate() {
+                if index > 0 {
+                    text.push_str(", ");
+                }
+                write_string(text, value);
+            }
+            text.push_str(" )");
+        }
+        Value::PoolVector2Array(values) => write_reals(
+            text,
+            "PoolVector2Array",
+            values.iter().copied().flat_map(vector2),
+        ),
+        Value::PoolVector3Array(values) => write_reals(
+            text,
+            "PoolVector3Array",
+            values.iter().copied().flat_map(vector3),
+        ),
+        Value::PoolColorArray(values) => write_reals(
+            text,
+            "PoolColorArray",
+            values.iter().copied().flat_map(color),
+        ),
+        Value::Resource(ResourceRef::External(id)) => {
+            let _ = write!(text, "ExtResource( {} )", id);
+        }
+        Value::Resource(ResourceRef::Sub(id)) => {
+            let _ = write!(text, "SubResource( {} )", id);
+        }
+        Value::Resource(ResourceRef::Path(path)) => {
+            text.push_str("Resource( ");
+            write_string(text, path);
+            text.push_str(" )");
+        }
+    }
+}
+
+fn write_ints<T: std::fmt::Display>(text: &mut String, name: &str, values: &[T]) {
+    text.push_str(name);
+    text.push_str("( ");
+    for (index, value) in values.iter().enumerate() {
+        if index > 0 {
+            text.push_str(", ");
+        }
+        let _ = write!(text, "{}", value);
+    }
+    text.push_str(" )");
+}
+
+#[cfg(test)]
+mod tests {
+    use gdnative::core_types::{Color, Vector2};
+
+    use crate::{
+        collections::{HashMap, HashSet},
+        text::{write, write_value},
+        value::{ResourceRef, Value},
+        vec::Vec,
+    };
+
+    use super::format_real;
+
+    #[test]
+    fn test_format_real() {
+        assert_eq!(format_real(0.0), "0");
+        assert_eq!(format_real(1.0), "1");
+        assert_eq!(format_real(0.1), "0.1");
+        assert_eq!(format_real(-2.5), "-2.5");
+        assert_eq!(format_real(1234567.0), "1.23457e+06");
+        assert_eq!(format_real(0.0001), "0.0001");
+        assert_eq!(format_real(0.00001), "1e-05");
+        assert_eq!(format_real(f64::from(0.1f32)), "0.1");
+    }
+
+    #[test]
+    fn test_primitives() {
+        assert_eq!(write_value(&Value::Nil), "null");
+        assert_eq!(write(&true), "true");
+        assert_eq!(write(&-3), "-3");
+        assert_eq!(write(&1.0), "1.0");
+        assert_eq!(write(&0.25), "0.25");
+        assert_eq!(write("say \"hi\"\n"), "\"say \\\"hi\\\"\n\"");
+    }
+
+    #[test]
+    fn test_constructors() {
+        assert_eq!(write(&Vector2::new(1.0, 2.5)), "Vector2( 1, 2.5 )");
+        assert_eq!(
+            write(&Color::from_rgba(1.0, 0.5, 0.0, 1.0)),
+            "Color( 1, 0.5, 0, 1 )"
+        );
+        assert_eq!(
+            write_value(&Value::NodePath("../a:b".to_string())),
+            "NodePath(\"../a:b\")"
+        );
+        assert_eq!(
+            write_value(&Value::PoolIntArray(vec![1, 2])),
+            "PoolIntArray( 1, 2 )"
+        );
+    }
+
+    #[test]
+    fn test_resources() {
+        let textures = Vec::from(vec![ResourceRef::External(1), ResourceRef::Sub(2)]);
+
+        assert_eq!(write(&textures), "[ ExtResource( 1 ), SubResource( 2 ) ]");
+        assert_eq!(
+            write(&ResourceRef::Path("res://icon.png".to_string())),
+            "Resource( \"res://icon.png\" )"
+        );
+    }
+
+    #[test]
+    fn test_vec() {
+        assert_eq!(write(&Vec::<i32>::new()), "[  ]");
+        assert_eq!(write(&Vec::from(vec![2, 1])), "[ 2, 1 ]");
+    }
+
+    #[test]
+    fn test_hash_set() {
+        assert_eq!(write(&HashSet::from([7])), "[ 7 ]");
+    }
+
+    #[test]
+    fn test_hash_map_sorted() {
+        let map = HashMap::from([(3, "c"), (1, "a"), (2, "b")]);
+
+        assert_eq!(write(&map), "{\n1: \"a\",\n2: \"b\",\n3: \"c\"\n}");
+        assert_eq!(write(&HashMap::<i32, i32>::new()), "{\n\n}");
+    }
+
+    #[test]
+    fn test_mixed_keys_sorted_by_type() {
+        let value = Value::Dictionary(vec![
+            (Value::String("a".to_string()), Value::Nil),
+            (Value::Int(1), Value::Vector2(Vector2::new(1.0, 2.0))),
+        ]);
+
+        assert_eq!(
+            write_value(&value),
+            "{\n1: Vector2( 1, 2 ),\n\"a\": null\n}"
+        );
+    }
+}
@@ -0,0 +1,7 @@
+#[allow(clippy::module_inception)]
+mod tracked;
+mod tracked_hash_map;
+mod tracked_hash_set;
+mod tracked_vec;
+
+pub use tracked::{Track, Tracked};
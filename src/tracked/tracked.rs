@@ -0,0 +1,192 @@
+use std::{fmt, ops::Deref};
+
+use gdnative::{
+    export::{Export, ExportInfo},
+    prelude::{FromVariant, FromVariantError, ToVariant, Variant},
+};
+
+/// A collection whose changes [`Tracked`] can record.
+///
+/// Implemented for [`HashMap`](crate::collections::HashMap),
+/// [`HashSet`](crate::collections::HashSet) and [`Vec`](crate::vec::Vec).
+pub trait Track {
+    /// What is recorded between two calls to `take_changes`.
+    type Log: Default;
+}
+
+/// A wrapper that records the changes made to a collection since the last
+/// call to `take_changes`.
+///
+/// The collection can be read through [`Deref`], but it can only be mutated
+/// through the methods of `Tracked`, which log what they change. Mutable
+/// references handed out by `get_mut`, `entry` or indexing count as a change
+/// of that element, whether or not it is written to.
+///
+/// `take_changes` returns a [`MapDelta`](crate::collections::MapDelta) for a
+/// `HashMap`, a [`SetDelta`](crate::collections::SetDelta) for a `HashSet`
+/// and a [`VecPatch`](crate::vec::VecPatch) for a `Vec`, which can be sent to
+/// peers and applied to their copies.
+///
+/// # Examples
+///
+/// ```
+/// use gdvariants::{collections::HashMap, tracked::Tracked};
+///
+/// let mut health: Tracked<HashMap<i64, i64>> = Tracked::new(HashMap::from([(1, 100)]));
+/// health.insert(2, 100);
+/// health[&1] -= 30;
+///
+/// let mut replica = HashMap::from([(1, 100)]);
+/// replica.apply(&health.take_changes());
+///
+/// assert_eq!(replica, *health);
+/// assert!(!health.has_changes());
+/// ```
+pub struct Tracked<T: Track> {
+    pub(crate) value: T,
+    pub(crate) log: T::Log,
+}
+
+impl<T: Track> Tracked<T> {
+    /// Wraps `value`, starting with no changes.
+    #[inline]
+    pub fn new(value: T) -> Tracked<T> {
+        Tracked {
+            value,
+            log: T::Log::default(),
+        }
+    }
+
+    /// Unwraps the value, discarding the recorded changes.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Forgets the recorded changes, as if they had been taken.
+    #[inline]
+    pub fn clear_changes(&mut self) {
+        self.log = T::Log::default();
+    }
+}
+
+impl<T: Track> Deref for Tracked<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> Export for Tracked<T>
+where
+    T: Track + Export,
+{
+    type Hint = T::Hint;
+
+    fn export_info(hint: Option<Self::Hint>) -> ExportInfo {
+        T::export_info(hint)
+    }
+}
+
+impl<T> FromVariant for Tracked<T>
+where
+    T: Track + FromVariant,
+{
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        Ok(Tracked::new(T::from_variant(variant)?))
+    }
+}
+
+impl<T> ToVariant for Tracked<T>
+where
+    T: Track + ToVariant,
+{
+    fn to_variant(&self) -> Variant {
+        self.value.to_variant()
+    }
+}
+
+impl<T> Clone for Tracked<T>
+where
+    T: Track + Clone,
+    T::Log: Clone,
+{
+    fn clone(&self) -> Tracked<T> {
+        Tracked {
+            value: self.value.clone(),
+            log: self.log.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Tracked<T>
+where
+    T: Track + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl<T> Default for Tracked<T>
+where
+    T: Track + Default,
+{
+    #[inline]
+    fn default() -> Tracked<T> {
+        Tracked::new(T::default())
+    }
+}
+
+impl<T: Track> From<T> for Tracked<T> {
+    #[inline]
+    fn from(value: T) -> Tracked<T> {
+        Tracked::new(value)
+    }
+}
+
+impl<T, U> PartialEq<Tracked<U>> for Tracked<T>
+where
+    T: Track + PartialEq<U>,
+    U: Track,
+{
+    #[inline]
+    fn eq(&self, other: &Tracked<U>) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T> Eq for Tracked<T> where T: Track + Eq {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{collections::HashMap, tracked::Tracked, vec::Vec};
+
+    #[test]
+    fn test_deref() {
+        let tracked = Tracked::new(Vec::from(vec![2, 1, 3]));
+
+        assert_eq!(tracked.len(), 3);
+        assert!(!tracked.has_changes());
+    }
+
+    #[test]
+    fn test_clear_changes() {
+        let mut tracked = Tracked::new(HashMap::new());
+        tracked.insert(1, 2);
+        tracked.clear_changes();
+
+        assert!(!tracked.has_changes());
+        assert!(tracked.take_changes().is_empty());
+    }
+
+    #[test]
+    fn test_into_inner() {
+        let mut tracked = Tracked::new(Vec::from(vec![2, 1]));
+        tracked.push(3);
+
+        assert_eq!(tracked.into_inner(), vec![2, 1, 3]);
+    }
+}
@@ -0,0 +1,267 @@
+use std::{
+    borrow::Borrow,
+    collections::hash_map::Entry,
+    hash::{BuildHasher, Hash},
+    ops::{Index, IndexMut},
+};
+
+use crate::{
+    collections::{HashMap, MapDelta},
+    tracked::{Track, Tracked},
+};
+
+impl<K, V, S> Track for HashMap<K, V, S> {
+    /// The touched keys, with whether each was in the map when the changes
+    /// were last taken.
+    type Log = std::collections::HashMap<K, bool>;
+}
+
+impl<K, V, S> Tracked<HashMap<K, V, S>>
+where
+    K: Eq + Hash + Clone,
+    S: BuildHasher,
+{
+    /// Returns `true` if a key was touched since the changes were last taken.
+    #[inline]
+    pub fn has_changes(&self) -> bool {
+        !self.log.is_empty()
+    }
+
+    /// Records `key` as touched, before it is changed.
+    fn touch(&mut self, key: &K) {
+        if !self.log.contains_key(key) {
+            let existed = self.value.base.contains_key(key);
+            self.log.insert(key.clone(), existed);
+        }
+    }
+
+    /// Records an existing key as touched, before it is changed.
+    fn touch_existing<Q>(&mut self, k: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if let Some((key, _)) = self.value.base.get_key_value(k) {
+            if !self.log.contains_key(k) {
+                self.log.insert(key.clone(), true);
+            }
+        }
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// See [`HashMap::insert`].
+    #[inline]
+    pub fn insert(&mut self, k: K, v: V) -> Option<V> {
+        self.touch(&k);
+        self.value.base.insert(k, v)
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key
+    /// was previously in the map.
+    #[inline]
+    pub fn remove<Q>(&mut self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.remove_entry(k).map(|(_, value)| value)
+    }
+
+    /// Removes a key from the map, returning the stored key and value if the
+    /// key was previously in the map.
+    pub fn remove_entry<Q>(&mut self, k: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (key, value) = self.value.base.remove_entry(k)?;
+        if !self.log.contains_key::<K>(&key) {
+            self.log.insert(key.clone(), true);
+        }
+        Some((key, value))
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key,
+    /// recording the key as changed.
+    #[inline]
+    pub fn get_mut<Q>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.touch_existing(k);
+        self.value.base.get_mut(k)
+    }
+
+    /// Gets the given key's corresponding entry in the map for in-place
+    /// manipulation, recording the key as changed.
+    #[inline]
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        self.touch(&key);
+        self.value.base.entry(key)
+    }
+
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// Unlike [`HashMap::retain`], the predicate cannot mutate the values.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        let log = &mut self.log;
+        self.value.base.retain(|key, value| {
+            let keep = f(key, value);
+            if !keep {
+                log.entry(key.clone()).or_insert(true);
+            }
+            keep
+        });
+    }
+
+    /// Clears the map, removing all key-value pairs.
+    pub fn clear(&mut self) {
+        for key in self.value.base.keys() {
+            self.log.entry(key.clone()).or_insert(true);
+        }
+        self.value.base.clear();
+    }
+
+    /// Returns the changes since they were last taken, and starts recording
+    /// anew.
+    ///
+    /// Applying the changes to a copy of the map as it was when they were
+    /// last taken makes it equal to this map.
+    pub fn take_changes(&mut self) -> MapDelta<K, V>
+    where
+        V: Clone,
+    {
+        let mut delta = MapDelta::new();
+        for (key, existed) in std::mem::take(&mut self.log) {
+            match (self.value.base.get(&key), existed) {
+                (Some(value), false) => {
+                    delta.inserted.insert(key, value.clone());
+                }
+                (Some(value), true) => {
+                    delta.changed.insert(key, value.clone());
+                }
+                (None, true) => delta.removed.push(key),
+                (None, false) => {}
+            }
+        }
+        delta
+    }
+}
+
+impl<K, Q: ?Sized, V, S> Index<&'_ Q> for Tracked<HashMap<K, V, S>>
+where
+    K: Eq + Hash + Borrow<Q>,
+    Q: Eq + Hash,
+    S: BuildHasher,
+{
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to the supplied key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the `HashMap`.
+    #[inline]
+    fn index(&self, key: &Q) -> &V {
+        self.value.base.index(key)
+    }
+}
+
+impl<K, Q: ?Sized, V, S> IndexMut<&'_ Q> for Tracked<HashMap<K, V, S>>
+where
+    K: Eq + Hash + Clone + Borrow<Q>,
+    Q: Eq + Hash,
+    S: BuildHasher,
+{
+    /// Returns a mutable reference to the value corresponding to the supplied
+    /// key, recording the key as changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the `HashMap`.
+    #[inline]
+    fn index_mut(&mut self, key: &Q) -> &mut V {
+        self.get_mut(key).expect("key not found")
+    }
+}
+
+impl<K, V, S> Extend<(K, V)> for Tracked<HashMap<K, V, S>>
+where
+    K: Eq + Hash + Clone,
+    S: BuildHasher,
+{
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{collections::HashMap, tracked::Tracked};
+
+    fn replicate(
+        mut tracked: Tracked<HashMap<i64, i64>>,
+        mutate: impl FnOnce(&mut Tracked<HashMap<i64, i64>>),
+    ) {
+        let mut replica = tracked.value.clone();
+        mutate(&mut tracked);
+        replica.apply(&tracked.take_changes());
+
+        assert_eq!(replica, *tracked);
+        assert!(!tracked.has_changes());
+    }
+
+    #[test]
+    fn test_take_changes() {
+        let mut tracked = Tracked::new(HashMap::from([(1, 10), (2, 20), (3, 30)]));
+        tracked.insert(4, 40);
+        tracked.remove(&2);
+        *tracked.get_mut(&3).unwrap() += 1;
+        tracked.insert(5, 50);
+        tracked.remove(&5);
+
+        let delta = tracked.take_changes();
+
+        assert_eq!(delta.inserted(), &HashMap::from([(4, 40)]));
+        assert_eq!(delta.changed(), &HashMap::from([(3, 31)]));
+        assert_eq!(delta.removed(), [2]);
+        assert!(tracked.take_changes().is_empty());
+    }
+
+    #[test]
+    fn test_entry_and_index_mut() {
+        let mut tracked = Tracked::new(HashMap::from([(1, 10)]));
+        *tracked.entry(2).or_insert(0) += 5;
+        tracked[&1] += 1;
+
+        let delta = tracked.take_changes();
+
+        assert_eq!(delta.inserted(), &HashMap::from([(2, 5)]));
+        assert_eq!(delta.changed(), &HashMap::from([(1, 11)]));
+    }
+
+    #[test]
+    fn test_replicate() {
+        let map = HashMap::from([(1, 10), (2, 20), (3, 30)]);
+
+        replicate(Tracked::new(map.clone()), |tracked| {
+            tracked.remove(&1);
+            tracked.insert(1, 11);
+        });
+        replicate(Tracked::new(map.clone()), |tracked| {
+            tracked.retain(|key, _| *key != 2);
+            tracked.extend([(5, 50), (6, 60)]);
+        });
+        replicate(Tracked::new(map), |tracked| {
+            tracked.clear();
+            tracked.insert(3, 33);
+        });
+    }
+}
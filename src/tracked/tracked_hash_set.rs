@@ -0,0 +1,155 @@
+use std::{
+    borrow::Borrow,
+    hash::{BuildHasher, Hash},
+};
+
+use crate::{
+    collections::{HashSet, SetDelta},
+    tracked::{Track, Tracked},
+};
+
+impl<T, S> Track for HashSet<T, S> {
+    /// The touched values, with whether each was in the set when the changes
+    /// were last taken.
+    type Log = std::collections::HashMap<T, bool>;
+}
+
+impl<T, S> Tracked<HashSet<T, S>>
+where
+    T: Eq + Hash + Clone,
+    S: BuildHasher,
+{
+    /// Returns `true` if a value was touched since the changes were last
+    /// taken.
+    #[inline]
+    pub fn has_changes(&self) -> bool {
+        !self.log.is_empty()
+    }
+
+    /// Adds a value to the set.
+    ///
+    /// See [`HashSet::insert`].
+    pub fn insert(&mut self, value: T) -> bool {
+        if !self.log.contains_key::<T>(&value) {
+            let existed = self.value.base.contains(&value);
+            self.log.insert(value.clone(), existed);
+        }
+        self.value.base.insert(value)
+    }
+
+    /// Removes a value from the set. Returns whether the value was present in
+    /// the set.
+    #[inline]
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.take(value).is_some()
+    }
+
+    /// Removes and returns the value in the set, if any, that is equal to the
+    /// given one.
+    pub fn take<Q>(&mut self, value: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let value = self.value.base.take(value)?;
+        if !self.log.contains_key::<T>(&value) {
+            self.log.insert(value.clone(), true);
+        }
+        Some(value)
+    }
+
+    /// Retains only the elements specified by the predicate.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let log = &mut self.log;
+        self.value.base.retain(|value| {
+            let keep = f(value);
+            if !keep {
+                log.entry(value.clone()).or_insert(true);
+            }
+            keep
+        });
+    }
+
+    /// Clears the set, removing all values.
+    pub fn clear(&mut self) {
+        for value in self.value.base.iter() {
+            self.log.entry(value.clone()).or_insert(true);
+        }
+        self.value.base.clear();
+    }
+
+    /// Returns the changes since they were last taken, and starts recording
+    /// anew.
+    pub fn take_changes(&mut self) -> SetDelta<T> {
+        let mut delta = SetDelta::new();
+        for (value, existed) in std::mem::take(&mut self.log) {
+            match (self.value.base.contains(&value), existed) {
+                (true, false) => {
+                    delta.inserted.insert(value);
+                }
+                (false, true) => {
+                    delta.removed.insert(value);
+                }
+                _ => {}
+            }
+        }
+        delta
+    }
+}
+
+impl<T, S> Extend<T> for Tracked<HashSet<T, S>>
+where
+    T: Eq + Hash + Clone,
+    S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{collections::HashSet, tracked::Tracked};
+
+    #[test]
+    fn test_take_changes() {
+        let mut tracked = Tracked::new(HashSet::from([1, 2, 3]));
+        tracked.insert(4);
+        tracked.remove(&2);
+        tracked.insert(5);
+        tracked.remove(&5);
+        tracked.remove(&3);
+        tracked.insert(3);
+
+        let delta = tracked.take_changes();
+
+        assert_eq!(delta.inserted(), &HashSet::from([4]));
+        assert_eq!(delta.removed(), &HashSet::from([2]));
+        assert!(!tracked.has_changes());
+    }
+
+    #[test]
+    fn test_replicate() {
+        let mut tracked = Tracked::new(HashSet::from([1, 2, 3]));
+        let mut replica = tracked.value.clone();
+
+        tracked.retain(|value| value % 2 == 1);
+        tracked.extend([6, 7]);
+        replica.apply(&tracked.take_changes());
+        assert_eq!(replica, *tracked);
+
+        tracked.clear();
+        tracked.insert(1);
+        replica.apply(&tracked.take_changes());
+        assert_eq!(replica, *tracked);
+    }
+}
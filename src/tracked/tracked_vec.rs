@@ -0,0 +1,369 @@
+use std::ops::{Index, IndexMut, Range};
+
+use crate::{
+    tracked::{Track, Tracked},
+    vec::{Vec, VecEdit, VecPatch},
+};
+
+/// The edits made to a [`Vec`] since the changes were last taken.
+pub struct VecLog<T> {
+    edits: std::vec::Vec<VecEdit<T>>,
+    /// Elements handed out by mutable reference. They are read into a
+    /// `Replace` edit once the reference is gone, on the next call.
+    pending: Option<Range<usize>>,
+}
+
+impl<T> Default for VecLog<T> {
+    fn default() -> VecLog<T> {
+        VecLog {
+            edits: std::vec::Vec::new(),
+            pending: None,
+        }
+    }
+}
+
+impl<T> Clone for VecLog<T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> VecLog<T> {
+        VecLog {
+            edits: self.edits.clone(),
+            pending: self.pending.clone(),
+        }
+    }
+}
+
+impl<T> Track for Vec<T> {
+    type Log = VecLog<T>;
+}
+
+impl<T> Tracked<Vec<T>>
+where
+    T: Clone,
+{
+    /// Returns `true` if the vector was changed since the changes were last
+    /// taken.
+    #[inline]
+    pub fn has_changes(&self) -> bool {
+        !self.log.edits.is_empty() || self.log.pending.is_some()
+    }
+
+    /// Records the elements that were handed out by mutable reference.
+    fn resolve(&mut self) {
+        if let Some(range) = self.log.pending.take() {
+            let values = Vec::from(self.value.base[range.clone()].to_vec());
+            self.record(VecEdit::Replace {
+                index: range.start,
+                values,
+            });
+        }
+    }
+
+    /// Appends an edit, merging it into the last one where they form a run.
+    fn record(&mut self, edit: VecEdit<T>) {
+        match (self.log.edits.last_mut(), edit) {
+            (
+                Some(VecEdit::Insert { index, values }),
+                VecEdit::Insert {
+                    index: next,
+                    values: more,
+                },
+            ) if next == *index + values.len() => values.extend(more),
+            (
+                Some(VecEdit::Remove { index, len }),
+                VecEdit::Remove {
+                    index: next,
+                    len: more,
+                },
+            ) if next == *index || next + more == *index => {
+                *index = next.min(*index);
+                *len += more;
+            }
+            (
+                Some(VecEdit::Replace { index, values }),
+                VecEdit::Replace {
+                    index: next,
+                    values: more,
+                },
+            ) if next <= *index + values.len() && *index <= next + more.len() => {
+                // The runs overlap or touch, the later one wins where they
+                // overlap.
+                let start = next.min(*index);
+                let end = (next + more.len()).max(*index + values.len());
+                let merged = (start..end)
+                    .map(|position| match position.checked_sub(next) {
+                        Some(offset) if offset < more.len() => more[offset].clone(),
+                        _ => values[position - *index].clone(),
+                    })
+                    .collect();
+                *index = start;
+                *values = merged;
+            }
+            (_, edit) => self.log.edits.push(edit),
+        }
+    }
+
+    /// Appends an element to the back of the vector.
+    pub fn push(&mut self, value: T) {
+        self.resolve();
+        self.record(VecEdit::Insert {
+            index: self.value.len(),
+            values: Vec::from(vec![value.clone()]),
+        });
+        self.value.base.push(value);
+    }
+
+    /// Removes the last element from the vector and returns it, or `None` if
+    /// it is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        self.resolve();
+        let value = self.value.base.pop()?;
+        self.record(VecEdit::Remove {
+            index: self.value.len(),
+            len: 1,
+        });
+        Some(value)
+    }
+
+    /// Inserts an element at position `index` within the vector, shifting
+    /// all elements after it to the right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, element: T) {
+        self.resolve();
+        let values = Vec::from(vec![element.clone()]);
+        self.value.base.insert(index, element);
+        self.record(VecEdit::Insert { index, values });
+    }
+
+    /// Removes and returns the element at position `index` within the
+    /// vector, shifting all elements after it to the left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        self.resolve();
+        let value = self.value.base.remove(index);
+        self.record(VecEdit::Remove { index, len: 1 });
+        value
+    }
+
+    /// Removes an element from the vector and returns it, replacing it with
+    /// the last element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        self.resolve();
+        let value = self.value.base.swap_remove(index);
+        let len = self.value.len();
+        if index < len {
+            self.record(VecEdit::Replace {
+                index,
+                values: Vec::from(vec![self.value.base[index].clone()]),
+            });
+        }
+        self.record(VecEdit::Remove { index: len, len: 1 });
+        value
+    }
+
+    /// Shortens the vector, keeping the first `len` elements and dropping
+    /// the rest.
+    pub fn truncate(&mut self, len: usize) {
+        self.resolve();
+        if len < self.value.len() {
+            self.record(VecEdit::Remove {
+                index: len,
+                len: self.value.len() - len,
+            });
+            self.value.base.truncate(len);
+        }
+    }
+
+    /// Clears the vector, removing all values.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Clones and appends all elements in a slice to the vector.
+    #[inline]
+    pub fn extend_from_slice(&mut self, other: &[T]) {
+        self.extend(other.iter().cloned());
+    }
+
+    /// Retains only the elements specified by the predicate.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.resolve();
+        let mut removed = std::vec::Vec::new();
+        let mut index = 0;
+        self.value.base.retain(|value| {
+            let keep = f(value);
+            if keep {
+                index += 1;
+            } else {
+                removed.push(index);
+            }
+            keep
+        });
+        for index in removed {
+            self.record(VecEdit::Remove { index, len: 1 });
+        }
+    }
+
+    /// Returns a mutable reference to an element, recording it as changed.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.resolve();
+        if index < self.value.len() {
+            self.log.pending = Some(index..index + 1);
+        }
+        self.value.base.get_mut(index)
+    }
+
+    /// Extracts a mutable slice of the entire vector, recording every element
+    /// as changed.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.resolve();
+        if !self.value.is_empty() {
+            self.log.pending = Some(0..self.value.len());
+        }
+        &mut self.value.base
+    }
+
+    /// Returns the changes since they were last taken, and starts recording
+    /// anew.
+    ///
+    /// Applying the changes to a copy of the vector as it was when they were
+    /// last taken makes it equal to this vector.
+    pub fn take_changes(&mut self) -> VecPatch<T> {
+        self.resolve();
+        VecPatch::from(std::mem::take(&mut self.log.edits))
+    }
+}
+
+impl<T> Index<usize> for Tracked<Vec<T>> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &T {
+        &self.value.base[index]
+    }
+}
+
+impl<T> IndexMut<usize> for Tracked<Vec<T>>
+where
+    T: Clone,
+{
+    /// Returns a mutable reference to an element, recording it as changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        self.get_mut(index).expect("index out of bounds")
+    }
+}
+
+impl<T> Extend<T> for Tracked<Vec<T>>
+where
+    T: Clone,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.resolve();
+        let values: std::vec::Vec<T> = iter.into_iter().collect();
+        if !values.is_empty() {
+            self.record(VecEdit::Insert {
+                index: self.value.len(),
+                values: Vec::from(values.clone()),
+            });
+            self.value.base.extend(values);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test_util::Rng,
+        tracked::Tracked,
+        vec::{Vec, VecEdit},
+    };
+
+    #[test]
+    fn test_take_changes() {
+        let mut tracked = Tracked::new(Vec::from(vec![1, 2, 3]));
+        tracked.push(4);
+        tracked.push(5);
+        tracked[0] = 10;
+        tracked.remove(1);
+
+        assert_eq!(
+            tracked.take_changes().edits(),
+            [
+                VecEdit::Insert {
+                    index: 3,
+                    values: Vec::from(vec![4, 5])
+                },
+                VecEdit::Replace {
+                    index: 0,
+                    values: Vec::from(vec![10])
+                },
+                VecEdit::Remove { index: 1, len: 1 },
+            ]
+        );
+        assert!(!tracked.has_changes());
+    }
+
+    #[test]
+    fn test_pending() {
+        let mut tracked = Tracked::new(Vec::from(vec![1, 2, 3]));
+        *tracked.get_mut(1).unwrap() = 20;
+        tracked.as_mut_slice()[2] = 30;
+
+        assert!(tracked.has_changes());
+        assert_eq!(
+            tracked.take_changes().edits(),
+            [VecEdit::Replace {
+                index: 0,
+                values: Vec::from(vec![1, 20, 30])
+            }]
+        );
+    }
+
+    #[test]
+    fn test_replicate_randomized() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        let mut next = |bound: usize| rng.next(bound as u64) as usize;
+
+        let mut tracked = Tracked::new(Vec::from(vec![0, 1, 2, 3]));
+        let mut replica = tracked.value.clone();
+        for round in 0..200 {
+            for _ in 0..next(6) {
+                let len = tracked.len();
+                match next(9) {
+                    0 => tracked.push(round),
+                    1 => drop(tracked.pop()),
+                    2 => tracked.insert(next(len + 1), round),
+                    3 if len > 0 => drop(tracked.remove(next(len))),
+                    4 if len > 0 => drop(tracked.swap_remove(next(len))),
+                    5 if len > 0 => tracked[next(len)] = round,
+                    6 => tracked.retain(|value| value % 3 != 0),
+                    7 => tracked.extend_from_slice(&[round, round + 1]),
+                    8 => tracked.truncate(next(len + 1)),
+                    _ => {}
+                }
+            }
+            replica.apply(&tracked.take_changes());
+            assert_eq!(replica, *tracked);
+        }
+    }
+}
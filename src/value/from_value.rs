@@ -0,0 +1,360 @@
+use std::{
+    convert::TryFrom,
+    error::Error,
+    fmt,
+    hash::{BuildHasher, Hash},
+};
+
+use gdnative::core_types::{
+    Aabb, Basis, Color, Plane, Quat, Rect2, Transform, Transform2D, VariantType, Vector2, Vector3,
+};
+
+use crate::{
+    collections::{HashMap, HashSet},
+    value::Value,
+    vec::Vec,
+};
+
+/// Types that can be converted from a [`Value`].
+///
+/// This is the engine-free counterpart of `FromVariant`, and accepts the same
+/// shapes: sequences are read from an `Array` or a pool array, maps from a
+/// `Dictionary`.
+pub trait FromValue: Sized {
+    fn from_value(value: &Value) -> Result<Self, FromValueError>;
+}
+
+/// Error returned by [`FromValue`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FromValueError {
+    /// The value has a different type than expected.
+    InvalidType {
+        expected: VariantType,
+        found: VariantType,
+    },
+    /// An integer does not fit into the target type.
+    OutOfRange { value: i64, target: &'static str },
+    /// An element of an array could not be converted.
+    InvalidItem {
+        index: usize,
+        error: Box<FromValueError>,
+    },
+    /// A key of a dictionary could not be converted.
+    InvalidKey { error: Box<FromValueError> },
+    /// A value of a dictionary could not be converted.
+    InvalidValue { error: Box<FromValueError> },
+    /// Any other error.
+    Custom(String),
+}
+
+impl FromValueError {
+    pub(crate) fn invalid_type(expected: VariantType, found: &Value) -> FromValueError {
+        FromValueError::InvalidType {
+            expected,
+            found: found.variant_type(),
+        }
+    }
+}
+
+impl fmt::Display for FromValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FromValueError::InvalidType { expected, found } => {
+                write!(f, "expected {:?}, found {:?}", expected, found)
+            }
+            FromValueError::OutOfRange { value, target } => {
+                write!(f, "{} does not fit into {}", value, target)
+            }
+            FromValueError::InvalidItem { index, error } => {
+                write!(f, "item {}: {}", index, error)
+            }
+            FromValueError::InvalidKey { error } => write!(f, "dictionary key: {}", error),
+            FromValueError::InvalidValue { error } => write!(f, "dictionary value: {}", error),
+            FromValueError::Custom(message) => f.write_str(message),
+        }
+    }
+}
+
+impl Error for FromValueError {}
+
+impl FromValue for Value {
+    #[inline]
+    fn from_value(value: &Value) -> Result<Self, FromValueError> {
+        Ok(value.clone())
+    }
+}
+
+impl FromValue for () {
+    fn from_value(value: &Value) -> Result<Self, FromValueError> {
+        match value {
+            Value::Nil => Ok(()),
+            _ => Err(FromValueError::invalid_type(VariantType::Nil, value)),
+        }
+    }
+}
+
+impl FromValue for bool {
+    fn from_value(value: &Value) -> Result<Self, FromValueError> {
+        value
+            .as_bool()
+            .ok_or_else(|| FromValueError::invalid_type(VariantType::Bool, value))
+    }
+}
+
+macro_rules! impl_from_value_int {
+    ($($ty:ty),*) => {
+        $(
+            impl FromValue for $ty {
+                fn from_value(value: &Value) -> Result<Self, FromValueError> {
+                    let int = value
+                        .as_int()
+                        .ok_or_else(|| FromValueError::invalid_type(VariantType::I64, value))?;
+                    <$ty>::try_from(int).map_err(|_| FromValueError::OutOfRange {
+                        value: int,
+                        target: stringify!($ty),
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_from_value_int!(i8, i16, i32, i64, u8, u16, u32, u64, usize);
+
+impl FromValue for f64 {
+    /// Reads a `Real`, or an `Int` as Godot converts it.
+    fn from_value(value: &Value) -> Result<Self, FromValueError> {
+        value
+            .as_real()
+            .ok_or_else(|| FromValueError::invalid_type(VariantType::F64, value))
+    }
+}
+
+impl FromValue for f32 {
+    /// Reads a `Real`, or an `Int` as Godot converts it.
+    fn from_value(value: &Value) -> Result<Self, FromValueError> {
+        f64::from_value(value).map(|real| real as f32)
+    }
+}
+
+impl FromValue for String {
+    fn from_value(value: &Value) -> Result<Self, FromValueError> {
+        value
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| FromValueError::invalid_type(VariantType::GodotString, value))
+    }
+}
+
+macro_rules! impl_from_value_math {
+    ($($ty:ident),*) => {
+        $(
+            impl FromValue for $ty {
+                fn from_value(value: &Value) -> Result<Self, FromValueError> {
+                    match value {
+                        Value::$ty(value) => Ok(*value),
+                        _ => Err(FromValueError::invalid_type(VariantType::$ty, value)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_value_math!(
+    Vector2,
+    Rect2,
+    Vector3,
+    Transform2D,
+    Plane,
+    Quat,
+    Aabb,
+    Basis,
+    Transform,
+    Color
+);
+
+impl<T> FromValue for Option<T>
+where
+    T: FromValue,
+{
+    /// Reads `Nil` as `None`.
+    fn from_value(value: &Value) -> Result<Self, FromValueError> {
+        match value {
+            Value::Nil => Ok(None),
+            _ => T::from_value(value).map(Some),
+        }
+    }
+}
+
+/// Converts the elements of an `Array` or pool array.
+pub(crate) fn from_array<T, C>(value: &Value) -> Result<C, FromValueError>
+where
+    T: FromValue,
+    C: FromIterator<T>,
+{
+    let convert = |(index, item): (usize, &Value)| {
+        T::from_value(item).map_err(|error| FromValueError::InvalidItem {
+            index,
+            error: Box::new(error),
+        })
+    };
+    match value {
+        Value::Array(items) => items.iter().enumerate().map(convert).collect(),
+        _ => match value.to_array() {
+            Some(items) => items.iter().enumerate().map(convert).collect(),
+            None => Err(FromValueError::invalid_type(
+                VariantType::VariantArray,
+                value,
+            )),
+        },
+    }
+}
+
+impl<T> FromValue for std::vec::Vec<T>
+where
+    T: FromValue,
+{
+    fn from_value(value: &Value) -> Result<Self, FromValueError> {
+        from_array(value)
+    }
+}
+
+impl<T> FromValue for Vec<T>
+where
+    T: FromValue,
+{
+    fn from_value(value: &Value) -> Result<Self, FromValueError> {
+        Ok(Vec {
+            base: from_array(value)?,
+        })
+    }
+}
+
+impl<T, S> FromValue for HashSet<T, S>
+where
+    T: Eq + Hash + FromValue,
+    S: BuildHasher + Default,
+{
+    fn from_value(value: &Value) -> Result<Self, FromValueError> {
+        Ok(HashSet {
+            base: from_array(value)?,
+        })
+    }
+}
+
+impl<K, V, S> FromValue for HashMap<K, V, S>
+where
+    K: Eq + Hash + FromValue,
+    V: FromValue,
+    S: BuildHasher + Default,
+{
+    fn from_value(value: &Value) -> Result<Self, FromValueError> {
+        let entries = value
+            .as_dictionary()
+            .ok_or_else(|| FromValueError::invalid_type(VariantType::Dictionary, value))?;
+        let mut hash_map: HashMap<K, V, S> =
+            HashMap::with_capacity_and_hasher(entries.len(), S::default());
+        for (key, value) in entries {
+            let key = K::from_value(key).map_err(|error| FromValueError::InvalidKey {
+                error: Box::new(error),
+            })?;
+            let value = V::from_value(value).map_err(|error| FromValueError::InvalidValue {
+                error: Box::new(error),
+            })?;
+            hash_map.insert(key, value);
+        }
+        Ok(hash_map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gdnative::core_types::{VariantType, Vector2};
+
+    use crate::{
+        collections::{HashMap, HashSet},
+        value::{FromValue, FromValueError, ToValue, Value},
+        vec::Vec,
+    };
+
+    #[test]
+    fn test_primitives() {
+        assert_eq!(bool::from_value(&Value::Bool(true)), Ok(true));
+        assert_eq!(u8::from_value(&Value::Int(3)), Ok(3));
+        assert_eq!(f64::from_value(&Value::Int(3)), Ok(3.0));
+        assert_eq!(Option::<i32>::from_value(&Value::Nil), Ok(None));
+    }
+
+    #[test]
+    fn test_invalid_type() {
+        assert_eq!(
+            i64::from_value(&Value::Bool(true)),
+            Err(FromValueError::InvalidType {
+                expected: VariantType::I64,
+                found: VariantType::Bool
+            })
+        );
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        assert_eq!(
+            u8::from_value(&Value::Int(256)),
+            Err(FromValueError::OutOfRange {
+                value: 256,
+                target: "u8"
+            })
+        );
+    }
+
+    #[test]
+    fn test_vec() {
+        let vec = Vec::from(vec![2, 1, 3]);
+
+        assert_eq!(Vec::from_value(&vec.to_value()), Ok(vec));
+    }
+
+    #[test]
+    fn test_vec_from_pool_array() {
+        let value = Value::PoolVector2Array(vec![Vector2::new(1.0, 2.0)]);
+
+        assert_eq!(
+            Vec::<Vector2>::from_value(&value),
+            Ok(Vec::from(vec![Vector2::new(1.0, 2.0)]))
+        );
+    }
+
+    #[test]
+    fn test_vec_invalid_item() {
+        let value = Value::Array(vec![Value::Int(1), Value::Nil]);
+
+        assert_eq!(
+            Vec::<i64>::from_value(&value),
+            Err(FromValueError::InvalidItem {
+                index: 1,
+                error: Box::new(FromValueError::InvalidType {
+                    expected: VariantType::I64,
+                    found: VariantType::Nil
+                })
+            })
+        );
+    }
+
+    #[test]
+    fn test_hash_set() {
+        let set = HashSet::from([2, 1, 3]);
+
+        assert_eq!(HashSet::from_value(&set.to_value()), Ok(set));
+    }
+
+    #[test]
+    fn test_hash_map() {
+        let map = HashMap::from([
+            ("a".to_string(), HashSet::from([1])),
+            ("b".to_string(), HashSet::from([2, 3])),
+        ]);
+
+        assert_eq!(HashMap::from_value(&map.to_value()), Ok(map));
+    }
+}
@@ -0,0 +1,39 @@
+//! A pure-Rust model of Godot's `Variant`.
+//!
+//! [`Value`] mirrors the types a Godot 3 `Variant` can hold, but unlike
+//! `Variant` it does not need a running engine. Conversion logic that is
+//! written against `Value` runs in plain `cargo test` and on headless servers.
+//! The [`ToValue`] and [`FromValue`] traits mirror `ToVariant` and
+//! `FromVariant`, and `Value` itself converts to and from `Variant` when the
+//! engine is present.
+//!
+//! ```
+//! use gdvariants::{
+//!     collections::HashMap,
+//!     value::{FromValue, ToValue, Value},
+//! };
+//!
+//! let map = HashMap::from([(1, "one".to_string())]);
+//! let value = map.to_value();
+//!
+//! assert_eq!(
+//!     value,
+//!     Value::Dictionary(vec![(Value::Int(1), Value::String("one".to_string()))])
+//! );
+//! assert_eq!(HashMap::from_value(&value), Ok(map));
+//! ```
+
+mod from_value;
+mod resource_ref;
+mod to_value;
+#[allow(clippy::module_inception)]
+mod value;
+mod value_variant;
+
+#[cfg(feature = "serde")]
+pub mod serde;
+
+pub use from_value::{FromValue, FromValueError};
+pub use resource_ref::{ResourceError, ResourceRef};
+pub use to_value::ToValue;
+pub use value::Value;
@@ -0,0 +1,51 @@
+use std::{error::Error, fmt};
+
+use gdnative::core_types::VariantType;
+
+use crate::value::{FromValue, FromValueError, ToValue, Value};
+
+/// A reference to a resource, as written in `.tres` and `.tscn` files.
+///
+/// Only has a meaning inside the resource file that declares the ids, so it
+/// cannot be converted to a `Variant` without the engine loading the file.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ResourceRef {
+    /// `ExtResource( id )`, a resource declared in an `[ext_resource]` section.
+    External(u32),
+    /// `SubResource( id )`, a resource declared in a `[sub_resource]` section.
+    Sub(u32),
+    /// `Resource( "path" )`, a resource loaded from its path.
+    Path(String),
+}
+
+/// Error returned when a [`Value`] holding a [`ResourceRef`] is converted to
+/// a `Variant` or encoded with [`marshal`](crate::marshal), since only the
+/// engine can resolve the reference.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResourceError {
+    pub resource: ResourceRef,
+}
+
+impl fmt::Display for ResourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} can only be resolved by the engine", self.resource)
+    }
+}
+
+impl Error for ResourceError {}
+
+impl ToValue for ResourceRef {
+    #[inline]
+    fn to_value(&self) -> Value {
+        Value::Resource(self.clone())
+    }
+}
+
+impl FromValue for ResourceRef {
+    fn from_value(value: &Value) -> Result<Self, FromValueError> {
+        match value {
+            Value::Resource(resource) => Ok(resource.clone()),
+            _ => Err(FromValueError::invalid_type(VariantType::Object, value)),
+        }
+    }
+}
@@ -0,0 +1,405 @@
+use serde::{
+    de::{
+        self, value::SeqDeserializer, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess,
+        VariantAccess, Visitor,
+    },
+    forward_to_deserialize_any,
+};
+
+use crate::value::{serde::Error, Value};
+
+/// A serde `Deserializer` that reads a [`Value`].
+///
+/// Pool arrays are read as sequences. Math types, such as `Vector2`, have no
+/// serde representation and fail to deserialize.
+#[derive(Clone, Copy, Debug)]
+pub struct Deserializer<'de> {
+    value: &'de Value,
+}
+
+impl<'de> Deserializer<'de> {
+    /// Creates a deserializer that reads `value`.
+    #[inline]
+    pub fn new(value: &'de Value) -> Deserializer<'de> {
+        Deserializer { value }
+    }
+}
+
+fn unsupported(value: &Value) -> Error {
+    Error::new(format!(
+        "cannot deserialize a {:?} Variant",
+        value.variant_type()
+    ))
+}
+
+/// Visits `values` as a sequence and fails if the visitor leaves any unread.
+fn visit_seq<'de, I, V>(values: I, visitor: V) -> Result<V::Value, Error>
+where
+    I: Iterator,
+    I::Item: IntoDeserializer<'de, Error>,
+    V: Visitor<'de>,
+{
+    let mut seq = SeqDeserializer::new(values);
+    let value = visitor.visit_seq(&mut seq)?;
+    seq.end()?;
+    Ok(value)
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Nil => visitor.visit_unit(),
+            Value::Bool(value) => visitor.visit_bool(*value),
+            Value::Int(value) => visitor.visit_i64(*value),
+            Value::Real(value) => visitor.visit_f64(*value),
+            Value::String(value) | Value::NodePath(value) => visitor.visit_borrowed_str(value),
+            Value::Array(values) => visit_seq(values.iter().map(Deserializer::new), visitor),
+            Value::Dictionary(entries) => {
+                let mut access = DictionaryAccess {
+                    entries: entries.iter(),
+                    value: None,
+                };
+                let value = visitor.visit_map(&mut access)?;
+                access.end(entries.len())?;
+                Ok(value)
+            }
+            Value::PoolByteArray(values) => visitor.visit_borrowed_bytes(values),
+            Value::PoolIntArray(values) => visit_seq(values.iter().copied(), visitor),
+            Value::PoolRealArray(values) => visit_seq(values.iter().copied(), visitor),
+            Value::PoolStringArray(values) => visit_seq(values.iter().map(String::as_str), visitor),
+            _ => Err(unsupported(self.value)),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Nil => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    /// Reads a `Dictionary` with the variant name as its only key, or the
+    /// variant name alone for unit variants.
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::String(variant) => visitor.visit_enum(VariantDeserializer {
+                variant,
+                value: None,
+            }),
+            Value::Dictionary(entries) => match entries.as_slice() {
+                [(Value::String(variant), value)] => visitor.visit_enum(VariantDeserializer {
+                    variant,
+                    value: Some(value),
+                }),
+                _ => Err(Error::new(
+                    "expected a Dictionary with the enum variant as its only key",
+                )),
+            },
+            _ => Err(Error::new(format!(
+                "expected an enum, found a {:?} Variant",
+                self.value.variant_type()
+            ))),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Deserializer<'de> {
+    type Deserializer = Self;
+
+    #[inline]
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+struct DictionaryAccess<'de> {
+    entries: std::slice::Iter<'de, (Value, Value)>,
+    value: Option<&'de Value>,
+}
+
+impl<'de> DictionaryAccess<'de> {
+    /// Fails if the visitor left entries unread, like `SeqDeserializer::end`.
+    fn end(&self, len: usize) -> Result<(), Error> {
+        match self.entries.len() {
+            0 => Ok(()),
+            remaining => Err(de::Error::invalid_length(
+                len,
+                &format!("{} entries in dictionary", len - remaining).as_str(),
+            )),
+        }
+    }
+}
+
+impl<'de> MapAccess<'de> for DictionaryAccess<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(Deserializer::new(key)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| Error::new("next_value called before next_key"))?;
+        seed.deserialize(Deserializer::new(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+struct VariantDeserializer<'de> {
+    variant: &'de str,
+    value: Option<&'de Value>,
+}
+
+impl<'de> EnumAccess<'de> for VariantDeserializer<'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self), Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant =
+            seed.deserialize(IntoDeserializer::<Error>::into_deserializer(self.variant))?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for VariantDeserializer<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self.value {
+            None | Some(Value::Nil) => Ok(()),
+            Some(value) => Err(Error::new(format!(
+                "expected Nil for unit variant {}, found a {:?} Variant",
+                self.variant,
+                value.variant_type()
+            ))),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(Deserializer::new(self.content()?))
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_seq(Deserializer::new(self.content()?), visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_map(Deserializer::new(self.content()?), visitor)
+    }
+}
+
+impl<'de> VariantDeserializer<'de> {
+    fn content(&self) -> Result<&'de Value, Error> {
+        self.value
+            .ok_or_else(|| Error::new(format!("variant {} is missing its value", self.variant)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gdnative::core_types::Vector2;
+    use serde::Deserialize;
+
+    use crate::{
+        collections::{HashMap, HashSet},
+        value::{
+            serde::{from_value, to_value},
+            Value,
+        },
+        vec::Vec,
+    };
+
+    #[derive(Deserialize, serde::Serialize, Debug, PartialEq)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Line(i32, i32),
+        Rect { w: i32, h: i32 },
+    }
+
+    #[derive(Deserialize, serde::Serialize, Debug, PartialEq)]
+    struct Player {
+        name: String,
+        level: u8,
+        tags: HashSet<String>,
+        items: HashMap<i64, Vec<Shape>>,
+        pet: Option<String>,
+    }
+
+    #[test]
+    fn test_primitives() {
+        assert_eq!(from_value::<bool>(&Value::Bool(true)), Ok(true));
+        assert_eq!(from_value::<u8>(&Value::Int(3)), Ok(3));
+        assert_eq!(from_value::<f32>(&Value::Int(3)), Ok(3.0));
+        assert_eq!(from_value::<Option<i32>>(&Value::Nil), Ok(None));
+        assert!(from_value::<u8>(&Value::Int(256)).is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let player = Player {
+            name: "Ada".to_string(),
+            level: 3,
+            tags: HashSet::from(["admin".to_string()]),
+            items: HashMap::from([(
+                1,
+                Vec::from(vec![
+                    Shape::Empty,
+                    Shape::Circle(1.5),
+                    Shape::Line(1, 2),
+                    Shape::Rect { w: 1, h: 2 },
+                ]),
+            )]),
+            pet: None,
+        };
+
+        let value = to_value(&player).unwrap();
+
+        assert_eq!(from_value::<Player>(&value), Ok(player));
+    }
+
+    #[test]
+    fn test_unit_variant_from_string() {
+        assert_eq!(
+            from_value::<Shape>(&Value::String("Empty".to_string())),
+            Ok(Shape::Empty)
+        );
+    }
+
+    #[test]
+    fn test_pool_array() {
+        assert_eq!(
+            from_value::<std::vec::Vec<i32>>(&Value::PoolIntArray(vec![2, 1])),
+            Ok(vec![2, 1])
+        );
+    }
+
+    #[test]
+    fn test_trailing_elements() {
+        let value = Value::Array(vec![Value::Int(1), Value::Int(2), Value::Int(3)]);
+
+        assert_eq!(
+            from_value::<(i64, i64)>(&value).unwrap_err().to_string(),
+            "invalid length 3, expected 2 elements in sequence"
+        );
+        assert!(from_value::<(i64, i64)>(&Value::PoolIntArray(vec![1, 2, 3])).is_err());
+        assert_eq!(from_value::<(i64, i64, i64)>(&value), Ok((1, 2, 3)));
+    }
+
+    #[test]
+    fn test_trailing_entries() {
+        struct First(i64);
+
+        impl<'de> Deserialize<'de> for First {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct FirstVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for FirstVisitor {
+                    type Value = First;
+
+                    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        f.write_str("a map")
+                    }
+
+                    fn visit_map<A: serde::de::MapAccess<'de>>(
+                        self,
+                        mut map: A,
+                    ) -> Result<First, A::Error> {
+                        let (_, value) = map.next_entry::<i64, i64>()?.unwrap();
+                        Ok(First(value))
+                    }
+                }
+
+                deserializer.deserialize_map(FirstVisitor)
+            }
+        }
+
+        let one = Value::Dictionary(vec![(Value::Int(1), Value::Int(2))]);
+        let two = Value::Dictionary(vec![
+            (Value::Int(1), Value::Int(2)),
+            (Value::Int(3), Value::Int(4)),
+        ]);
+
+        assert_eq!(from_value::<First>(&one).map(|first| first.0), Ok(2));
+        assert_eq!(
+            from_value::<First>(&two).err().unwrap().to_string(),
+            "invalid length 2, expected 1 entries in dictionary"
+        );
+    }
+
+    #[test]
+    fn test_unsupported() {
+        let value = Value::Vector2(Vector2::new(1.0, 2.0));
+
+        assert_eq!(
+            from_value::<i32>(&value).unwrap_err().to_string(),
+            "cannot deserialize a Vector2 Variant"
+        );
+    }
+}
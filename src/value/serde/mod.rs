@@ -0,0 +1,83 @@
+//! A serde `Serializer` and `Deserializer` for [`Value`] and `Variant`.
+//!
+//! Any `Serialize` type can become a `Variant` without deriving
+//! `ToVariant`, and any `Deserialize` type can be read back:
+//!
+//! - booleans, integers, floats and strings become the matching primitive,
+//! - sequences, tuples and tuple structs become an `Array`,
+//! - maps and structs become a `Dictionary`, with field names as keys,
+//! - `None`, `()` and unit structs become `Nil`,
+//! - byte buffers become a `PoolByteArray`,
+//! - enum variants become a `Dictionary` with the variant name as its only
+//!   key, the same representation the gdnative `ToVariant` derive uses.
+//!
+//! ```
+//! use gdvariants::{
+//!     collections::HashMap,
+//!     value::{serde::{from_value, to_value}, Value},
+//! };
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! struct Player {
+//!     name: String,
+//!     scores: HashMap<String, i64>,
+//! }
+//!
+//! let player = Player {
+//!     name: "Ada".to_string(),
+//!     scores: HashMap::from([("level1".to_string(), 10)]),
+//! };
+//! let value = to_value(&player).unwrap();
+//!
+//! assert_eq!(
+//!     value.get(&Value::String("name".to_string())),
+//!     Some(&Value::String("Ada".to_string()))
+//! );
+//! assert_eq!(from_value::<Player>(&value).unwrap(), player);
+//! ```
+
+mod deserializer;
+mod error;
+mod serializer;
+
+use gdnative::prelude::{FromVariant, Variant};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+pub use deserializer::Deserializer;
+pub use error::Error;
+pub use serializer::Serializer;
+
+use crate::value::Value;
+
+/// Serializes `value` into a [`Value`].
+pub fn to_value<T>(value: &T) -> Result<Value, Error>
+where
+    T: Serialize + ?Sized,
+{
+    value.serialize(Serializer)
+}
+
+/// Deserializes a `T` from a [`Value`].
+pub fn from_value<'de, T>(value: &'de Value) -> Result<T, Error>
+where
+    T: Deserialize<'de>,
+{
+    T::deserialize(Deserializer::new(value))
+}
+
+/// Serializes `value` into a `Variant`.
+pub fn to_variant<T>(value: &T) -> Result<Variant, Error>
+where
+    T: Serialize + ?Sized,
+{
+    Variant::try_from(&to_value(value)?).map_err(|error| Error::new(error.to_string()))
+}
+
+/// Deserializes a `T` from a `Variant`.
+pub fn from_variant<T>(variant: &Variant) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    from_value(&Value::from_variant(variant)?)
+}
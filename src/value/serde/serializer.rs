@@ -0,0 +1,455 @@
+use serde::{ser, Serialize};
+
+use crate::value::{serde::Error, Value};
+
+/// A serde `Serializer` that produces a [`Value`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = Value;
+    type Error = Error;
+
+    type SerializeSeq = SerializeArray;
+    type SerializeTuple = SerializeArray;
+    type SerializeTupleStruct = SerializeArray;
+    type SerializeTupleVariant = SerializeVariant<SerializeArray>;
+    type SerializeMap = SerializeDictionary;
+    type SerializeStruct = SerializeDictionary;
+    type SerializeStructVariant = SerializeVariant<SerializeDictionary>;
+
+    fn serialize_bool(self, v: bool) -> Result<Value, Error> {
+        Ok(Value::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Value, Error> {
+        Ok(Value::Int(v.into()))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Value, Error> {
+        Ok(Value::Int(v.into()))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Value, Error> {
+        Ok(Value::Int(v.into()))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Value, Error> {
+        Ok(Value::Int(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Value, Error> {
+        Ok(Value::Int(v.into()))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Value, Error> {
+        Ok(Value::Int(v.into()))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Value, Error> {
+        Ok(Value::Int(v.into()))
+    }
+
+    /// Fails if `v` does not fit into the signed 64-bit integer of a
+    /// `Variant`.
+    fn serialize_u64(self, v: u64) -> Result<Value, Error> {
+        i64::try_from(v)
+            .map(Value::Int)
+            .map_err(|_| Error::new(format!("{} does not fit into a Variant integer", v)))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value, Error> {
+        Ok(Value::Real(v.into()))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value, Error> {
+        Ok(Value::Real(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value, Error> {
+        Ok(Value::String(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value, Error> {
+        Ok(Value::String(v.to_string()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value, Error> {
+        Ok(Value::PoolByteArray(v.to_vec()))
+    }
+
+    fn serialize_none(self) -> Result<Value, Error> {
+        Ok(Value::Nil)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Value, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value, Error> {
+        Ok(Value::Nil)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, Error> {
+        Ok(Value::Nil)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Value, Error> {
+        Ok(tagged(variant, Value::Nil))
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<Value, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        Ok(tagged(variant, value.serialize(self)?))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeArray, Error> {
+        Ok(SerializeArray::with_capacity(len.unwrap_or_default()))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeArray, Error> {
+        Ok(SerializeArray::with_capacity(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeArray, Error> {
+        Ok(SerializeArray::with_capacity(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVariant<SerializeArray>, Error> {
+        Ok(SerializeVariant {
+            variant,
+            inner: SerializeArray::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<SerializeDictionary, Error> {
+        Ok(SerializeDictionary::with_capacity(len.unwrap_or_default()))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeDictionary, Error> {
+        Ok(SerializeDictionary::with_capacity(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVariant<SerializeDictionary>, Error> {
+        Ok(SerializeVariant {
+            variant,
+            inner: SerializeDictionary::with_capacity(len),
+        })
+    }
+}
+
+fn tagged(variant: &str, value: Value) -> Value {
+    Value::Dictionary(vec![(Value::String(variant.to_string()), value)])
+}
+
+#[doc(hidden)]
+pub struct SerializeArray {
+    values: std::vec::Vec<Value>,
+}
+
+impl SerializeArray {
+    fn with_capacity(capacity: usize) -> SerializeArray {
+        SerializeArray {
+            values: std::vec::Vec::with_capacity(capacity),
+        }
+    }
+
+    fn push<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.values.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for SerializeArray {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(Value::Array(self.values))
+    }
+}
+
+impl ser::SerializeTuple for SerializeArray {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(Value::Array(self.values))
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeArray {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(Value::Array(self.values))
+    }
+}
+
+#[doc(hidden)]
+pub struct SerializeDictionary {
+    entries: std::vec::Vec<(Value, Value)>,
+    key: Option<Value>,
+}
+
+impl SerializeDictionary {
+    fn with_capacity(capacity: usize) -> SerializeDictionary {
+        SerializeDictionary {
+            entries: std::vec::Vec::with_capacity(capacity),
+            key: None,
+        }
+    }
+
+    fn insert<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.entries
+            .push((Value::String(key.to_string()), value.serialize(Serializer)?));
+        Ok(())
+    }
+}
+
+impl ser::SerializeMap for SerializeDictionary {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.key = Some(key.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| Error::new("serialize_value called before serialize_key"))?;
+        self.entries.push((key, value.serialize(Serializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(Value::Dictionary(self.entries))
+    }
+}
+
+impl ser::SerializeStruct for SerializeDictionary {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(Value::Dictionary(self.entries))
+    }
+}
+
+#[doc(hidden)]
+pub struct SerializeVariant<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+impl ser::SerializeTupleVariant for SerializeVariant<SerializeArray> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.inner.push(value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(tagged(self.variant, Value::Array(self.inner.values)))
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeVariant<SerializeDictionary> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.inner.insert(key, value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(tagged(self.variant, Value::Dictionary(self.inner.entries)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use crate::{
+        collections::HashSet,
+        value::{serde::to_value, Value},
+        vec::Vec,
+    };
+
+    #[derive(Serialize)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Line(i32, i32),
+        Rect { w: i32, h: i32 },
+    }
+
+    fn string(value: &str) -> Value {
+        Value::String(value.to_string())
+    }
+
+    #[test]
+    fn test_primitives() {
+        assert_eq!(to_value(&true), Ok(Value::Bool(true)));
+        assert_eq!(to_value(&3u8), Ok(Value::Int(3)));
+        assert_eq!(to_value(&0.5f32), Ok(Value::Real(0.5)));
+        assert_eq!(to_value(&'a'), Ok(string("a")));
+        assert_eq!(to_value(&None::<i32>), Ok(Value::Nil));
+    }
+
+    #[test]
+    fn test_u64_out_of_range() {
+        assert!(to_value(&u64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_struct() {
+        #[derive(Serialize)]
+        struct Player {
+            name: String,
+            tags: HashSet<String>,
+            scores: Vec<(i32, bool)>,
+        }
+
+        let player = Player {
+            name: "Ada".to_string(),
+            tags: HashSet::from(["admin".to_string()]),
+            scores: Vec::from(vec![(1, true)]),
+        };
+
+        assert_eq!(
+            to_value(&player),
+            Ok(Value::Dictionary(vec![
+                (string("name"), string("Ada")),
+                (string("tags"), Value::Array(vec![string("admin")])),
+                (
+                    string("scores"),
+                    Value::Array(vec![Value::Array(vec![Value::Int(1), Value::Bool(true)])])
+                ),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_enum() {
+        let tagged = |name: &str, value| Value::Dictionary(vec![(string(name), value)]);
+
+        assert_eq!(to_value(&Shape::Empty), Ok(tagged("Empty", Value::Nil)));
+        assert_eq!(
+            to_value(&Shape::Circle(1.5)),
+            Ok(tagged("Circle", Value::Real(1.5)))
+        );
+        assert_eq!(
+            to_value(&Shape::Line(1, 2)),
+            Ok(tagged(
+                "Line",
+                Value::Array(vec![Value::Int(1), Value::Int(2)])
+            ))
+        );
+        assert_eq!(
+            to_value(&Shape::Rect { w: 1, h: 2 }),
+            Ok(tagged(
+                "Rect",
+                Value::Dictionary(vec![
+                    (string("w"), Value::Int(1)),
+                    (string("h"), Value::Int(2)),
+                ])
+            ))
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use gdnative::core_types::{
+    Aabb, Basis, Color, Plane, Quat, Rect2, Transform, Transform2D, Vector2, Vector3,
+};
+
+use crate::{
+    collections::{HashMap, HashSet},
+    value::Value,
+    vec::Vec,
+};
+
+/// Types that can be converted to a [`Value`].
+///
+/// This is the engine-free counterpart of `ToVariant`, and produces the same
+/// shape: sequences become an `Array` and maps a `Dictionary`.
+pub trait ToValue {
+    fn to_value(&self) -> Value;
+}
+
+impl ToValue for Value {
+    #[inline]
+    fn to_value(&self) -> Value {
+        self.clone()
+    }
+}
+
+impl ToValue for () {
+    #[inline]
+    fn to_value(&self) -> Value {
+        Value::Nil
+    }
+}
+
+impl ToValue for bool {
+    #[inline]
+    fn to_value(&self) -> Value {
+        Value::Bool(*self)
+    }
+}
+
+macro_rules! impl_to_value {
+    ($variant:ident as $target:ty: $($ty:ty),*) => {
+        $(
+            impl ToValue for $ty {
+                #[inline]
+                fn to_value(&self) -> Value {
+                    Value::$variant(<$target>::from(*self))
+                }
+            }
+        )*
+    };
+}
+
+impl_to_value!(Int as i64: i8, i16, i32, i64, u8, u16, u32);
+impl_to_value!(Real as f64: f32, f64);
+
+macro_rules! impl_to_value_saturating {
+    ($($ty:ty),*) => {
+        $(
+            impl ToValue for $ty {
+                /// Saturates values above `i64::MAX`, so they never turn
+                /// negative. Serializing such a value fails instead.
+                #[inline]
+                fn to_value(&self) -> Value {
+                    Value::Int(i64::try_from(*self).unwrap_or(i64::MAX))
+                }
+            }
+        )*
+    };
+}
+
+impl_to_value_saturating!(u64, usize);
+impl_to_value!(Vector2 as Vector2: Vector2);
+impl_to_value!(Rect2 as Rect2: Rect2);
+impl_to_value!(Vector3 as Vector3: Vector3);
+impl_to_value!(Transform2D as Transform2D: Transform2D);
+impl_to_value!(Plane as Plane: Plane);
+impl_to_value!(Quat as Quat: Quat);
+impl_to_value!(Aabb as Aabb: Aabb);
+impl_to_value!(Basis as Basis: Basis);
+impl_to_value!(Transform as Transform: Transform);
+impl_to_value!(Color as Color: Color);
+
+impl ToValue for str {
+    #[inline]
+    fn to_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
+
+impl ToValue for String {
+    #[inline]
+    fn to_value(&self) -> Value {
+        Value::String(self.clone())
+    }
+}
+
+impl<T> ToValue for &T
+where
+    T: ToValue + ?Sized,
+{
+    #[inline]
+    fn to_value(&self) -> Value {
+        T::to_value(self)
+    }
+}
+
+impl<T> ToValue for Option<T>
+where
+    T: ToValue,
+{
+    /// Converts `None` to `Nil`.
+    #[inline]
+    fn to_value(&self) -> Value {
+        match self {
+            Some(value) => value.to_value(),
+            None => Value::Nil,
+        }
+    }
+}
+
+impl<T> ToValue for [T]
+where
+    T: ToValue,
+{
+    fn to_value(&self) -> Value {
+        Value::Array(self.iter().map(ToValue::to_value).collect())
+    }
+}
+
+impl<T> ToValue for std::vec::Vec<T>
+where
+    T: ToValue,
+{
+    #[inline]
+    fn to_value(&self) -> Value {
+        self.as_slice().to_value()
+    }
+}
+
+impl<T> ToValue for Vec<T>
+where
+    T: ToValue,
+{
+    #[inline]
+    fn to_value(&self) -> Value {
+        self.base.to_value()
+    }
+}
+
+impl<T, S> ToValue for HashSet<T, S>
+where
+    T: ToValue,
+{
+    fn to_value(&self) -> Value {
+        Value::Array(self.base.iter().map(ToValue::to_value).collect())
+    }
+}
+
+impl<K, V, S> ToValue for HashMap<K, V, S>
+where
+    K: ToValue,
+    V: ToValue,
+{
+    fn to_value(&self) -> Value {
+        Value::Dictionary(
+            self.base
+                .iter()
+                .map(|(key, value)| (key.to_value(), value.to_value()))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        collections::{HashMap, HashSet},
+        value::{ToValue, Value},
+        vec::Vec,
+    };
+
+    #[test]
+    fn test_primitives() {
+        assert_eq!(true.to_value(), Value::Bool(true));
+        assert_eq!(3u8.to_value(), Value::Int(3));
+        assert_eq!(3usize.to_value(), Value::Int(3));
+        assert_eq!(u64::MAX.to_value(), Value::Int(i64::MAX));
+        assert_eq!(0.5f32.to_value(), Value::Real(0.5));
+        assert_eq!("a".to_value(), Value::String("a".to_string()));
+        assert_eq!(None::<i32>.to_value(), Value::Nil);
+    }
+
+    #[test]
+    fn test_vec() {
+        let vec = Vec::from(vec![2, 1, 3]);
+
+        assert_eq!(
+            vec.to_value(),
+            Value::Array(vec![Value::Int(2), Value::Int(1), Value::Int(3)])
+        );
+    }
+
+    #[test]
+    fn test_hash_set() {
+        let set = HashSet::from([2]);
+
+        assert_eq!(set.to_value(), Value::Array(vec![Value::Int(2)]));
+    }
+
+    #[test]
+    fn test_hash_map() {
+        let map = HashMap::from([(2, Vec::from(vec![true]))]);
+
+        assert_eq!(
+            map.to_value(),
+            Value::Dictionary(vec![(Value::Int(2), Value::Array(vec![Value::Bool(true)]))])
+        );
+    }
+}
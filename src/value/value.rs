@@ -0,0 +1,213 @@
+use gdnative::core_types::{
+    Aabb, Basis, Color, Plane, Quat, Rect2, Transform, Transform2D, VariantType, Vector2, Vector3,
+};
+
+use crate::value::ResourceRef;
+
+/// A Godot `Variant` value that can be created and inspected without the
+/// engine.
+///
+/// Dictionaries are stored as a list of key-value pairs in insertion order,
+/// the same order Godot keeps. Keys are compared with `==`, so lookups with
+/// [`get`](Value::get) are linear.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Value {
+    #[default]
+    Nil,
+    Bool(bool),
+    Int(i64),
+    Real(f64),
+    String(String),
+    Vector2(Vector2),
+    Rect2(Rect2),
+    Vector3(Vector3),
+    Transform2D(Transform2D),
+    Plane(Plane),
+    Quat(Quat),
+    Aabb(Aabb),
+    Basis(Basis),
+    Transform(Transform),
+    Color(Color),
+    NodePath(String),
+    Dictionary(std::vec::Vec<(Value, Value)>),
+    Array(std::vec::Vec<Value>),
+    PoolByteArray(std::vec::Vec<u8>),
+    PoolIntArray(std::vec::Vec<i32>),
+    PoolRealArray(std::vec::Vec<f32>),
+    PoolStringArray(std::vec::Vec<String>),
+    PoolVector2Array(std::vec::Vec<Vector2>),
+    PoolVector3Array(std::vec::Vec<Vector3>),
+    PoolColorArray(std::vec::Vec<Color>),
+    /// A reference to a resource in a `.tres` or `.tscn` file.
+    Resource(ResourceRef),
+}
+
+impl Value {
+    /// Returns the type of the `Variant` this value corresponds to.
+    pub fn variant_type(&self) -> VariantType {
+        match self {
+            Value::Nil => VariantType::Nil,
+            Value::Bool(_) => VariantType::Bool,
+            Value::Int(_) => VariantType::I64,
+            Value::Real(_) => VariantType::F64,
+            Value::String(_) => VariantType::GodotString,
+            Value::Vector2(_) => VariantType::Vector2,
+            Value::Rect2(_) => VariantType::Rect2,
+            Value::Vector3(_) => VariantType::Vector3,
+            Value::Transform2D(_) => VariantType::Transform2D,
+            Value::Plane(_) => VariantType::Plane,
+            Value::Quat(_) => VariantType::Quat,
+            Value::Aabb(_) => VariantType::Aabb,
+            Value::Basis(_) => VariantType::Basis,
+            Value::Transform(_) => VariantType::Transform,
+            Value::Color(_) => VariantType::Color,
+            Value::NodePath(_) => VariantType::NodePath,
+            Value::Dictionary(_) => VariantType::Dictionary,
+            Value::Array(_) => VariantType::VariantArray,
+            Value::PoolByteArray(_) => VariantType::ByteArray,
+            Value::PoolIntArray(_) => VariantType::Int32Array,
+            Value::PoolRealArray(_) => VariantType::Float32Array,
+            Value::PoolStringArray(_) => VariantType::StringArray,
+            Value::PoolVector2Array(_) => VariantType::Vector2Array,
+            Value::PoolVector3Array(_) => VariantType::Vector3Array,
+            Value::PoolColorArray(_) => VariantType::ColorArray,
+            Value::Resource(_) => VariantType::Object,
+        }
+    }
+
+    /// Returns `true` if the value is `Nil`.
+    #[inline]
+    pub fn is_nil(&self) -> bool {
+        matches!(self, Value::Nil)
+    }
+
+    /// Returns the boolean, if the value is a `Bool`.
+    #[inline]
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the integer, if the value is an `Int`.
+    #[inline]
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            Value::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the number, if the value is a `Real` or an `Int`.
+    #[inline]
+    pub fn as_real(&self) -> Option<f64> {
+        match self {
+            Value::Real(value) => Some(*value),
+            Value::Int(value) => Some(*value as f64),
+            _ => None,
+        }
+    }
+
+    /// Returns the string, if the value is a `String`.
+    #[inline]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements, if the value is an `Array`.
+    #[inline]
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Returns the entries, if the value is a `Dictionary`.
+    #[inline]
+    pub fn as_dictionary(&self) -> Option<&[(Value, Value)]> {
+        match self {
+            Value::Dictionary(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    /// Returns the value stored under `key`, if the value is a `Dictionary`
+    /// containing it.
+    pub fn get(&self, key: &Value) -> Option<&Value> {
+        self.as_dictionary()?
+            .iter()
+            .find(|(entry_key, _)| entry_key == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns the elements of an `Array` or of any pool array, converted to
+    /// values.
+    pub fn to_array(&self) -> Option<std::vec::Vec<Value>> {
+        fn map<T: Copy>(values: &[T], f: fn(T) -> Value) -> Option<std::vec::Vec<Value>> {
+            Some(values.iter().copied().map(f).collect())
+        }
+
+        match self {
+            Value::Array(values) => Some(values.clone()),
+            Value::PoolByteArray(values) => map(values, |v| Value::Int(v.into())),
+            Value::PoolIntArray(values) => map(values, |v| Value::Int(v.into())),
+            Value::PoolRealArray(values) => map(values, |v| Value::Real(v.into())),
+            Value::PoolStringArray(values) => {
+                Some(values.iter().cloned().map(Value::String).collect())
+            }
+            Value::PoolVector2Array(values) => map(values, Value::Vector2),
+            Value::PoolVector3Array(values) => map(values, Value::Vector3),
+            Value::PoolColorArray(values) => map(values, Value::Color),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gdnative::core_types::{VariantType, Vector2};
+
+    use crate::value::Value;
+
+    #[test]
+    fn test_variant_type() {
+        assert_eq!(Value::Nil.variant_type(), VariantType::Nil);
+        assert_eq!(Value::Int(1).variant_type(), VariantType::I64);
+        assert_eq!(
+            Value::Array(vec![]).variant_type(),
+            VariantType::VariantArray
+        );
+    }
+
+    #[test]
+    fn test_get() {
+        let value = Value::Dictionary(vec![
+            (Value::Int(1), Value::Bool(true)),
+            (Value::String("a".to_string()), Value::Nil),
+        ]);
+
+        assert_eq!(value.get(&Value::Int(1)), Some(&Value::Bool(true)));
+        assert_eq!(value.get(&Value::Int(2)), None);
+        assert_eq!(Value::Int(1).get(&Value::Int(1)), None);
+    }
+
+    #[test]
+    fn test_to_array() {
+        let value = Value::PoolVector2Array(vec![Vector2::new(1.0, 2.0)]);
+
+        assert_eq!(
+            value.to_array(),
+            Some(vec![Value::Vector2(Vector2::new(1.0, 2.0))])
+        );
+        assert_eq!(
+            Value::PoolIntArray(vec![2, 1]).to_array(),
+            Some(vec![Value::Int(2), Value::Int(1)])
+        );
+        assert_eq!(Value::Nil.to_array(), None);
+    }
+}
@@ -0,0 +1,124 @@
+use gdnative::{
+    core_types::{
+        Dictionary, FromVariantError, GodotString, NodePath, PoolArray, VariantArray,
+        VariantDispatch, VariantType,
+    },
+    prelude::{FromVariant, OwnedToVariant, ToVariant, Variant},
+};
+
+use crate::value::{ResourceError, Value};
+
+impl TryFrom<&Value> for Variant {
+    type Error = ResourceError;
+
+    /// Fails on resource references, since only the engine can resolve them.
+    fn try_from(value: &Value) -> Result<Variant, ResourceError> {
+        Ok(match value {
+            Value::Nil => Variant::nil(),
+            Value::Bool(value) => value.to_variant(),
+            Value::Int(value) => value.to_variant(),
+            Value::Real(value) => value.to_variant(),
+            Value::String(value) => value.to_variant(),
+            Value::Vector2(value) => value.to_variant(),
+            Value::Rect2(value) => value.to_variant(),
+            Value::Vector3(value) => value.to_variant(),
+            Value::Transform2D(value) => value.to_variant(),
+            Value::Plane(value) => value.to_variant(),
+            Value::Quat(value) => value.to_variant(),
+            Value::Aabb(value) => value.to_variant(),
+            Value::Basis(value) => value.to_variant(),
+            Value::Transform(value) => value.to_variant(),
+            Value::Color(value) => value.to_variant(),
+            Value::NodePath(value) => NodePath::from_str(value).to_variant(),
+            Value::Dictionary(entries) => {
+                let dictionary = Dictionary::new();
+                for (key, value) in entries {
+                    dictionary.insert(Variant::try_from(key)?, Variant::try_from(value)?);
+                }
+                dictionary.owned_to_variant()
+            }
+            Value::Array(values) => {
+                let array = VariantArray::new();
+                for value in values {
+                    array.push(Variant::try_from(value)?);
+                }
+                array.owned_to_variant()
+            }
+            Value::PoolByteArray(values) => PoolArray::from_vec(values.clone()).to_variant(),
+            Value::PoolIntArray(values) => PoolArray::from_vec(values.clone()).to_variant(),
+            Value::PoolRealArray(values) => PoolArray::from_vec(values.clone()).to_variant(),
+            Value::PoolStringArray(values) => {
+                PoolArray::from_vec(values.iter().map(GodotString::from_str).collect()).to_variant()
+            }
+            Value::PoolVector2Array(values) => PoolArray::from_vec(values.clone()).to_variant(),
+            Value::PoolVector3Array(values) => PoolArray::from_vec(values.clone()).to_variant(),
+            Value::PoolColorArray(values) => PoolArray::from_vec(values.clone()).to_variant(),
+            Value::Resource(resource) => {
+                return Err(ResourceError {
+                    resource: resource.clone(),
+                })
+            }
+        })
+    }
+}
+
+impl FromVariant for Value {
+    /// Fails on `Object` and `Rid`, which only exist inside the engine.
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        Ok(match variant.dispatch() {
+            VariantDispatch::Nil => Value::Nil,
+            VariantDispatch::Bool(value) => Value::Bool(value),
+            VariantDispatch::I64(value) => Value::Int(value),
+            VariantDispatch::F64(value) => Value::Real(value),
+            VariantDispatch::GodotString(value) => Value::String(value.to_string()),
+            VariantDispatch::Vector2(value) => Value::Vector2(value),
+            VariantDispatch::Rect2(value) => Value::Rect2(value),
+            VariantDispatch::Vector3(value) => Value::Vector3(value),
+            VariantDispatch::Transform2D(value) => Value::Transform2D(value),
+            VariantDispatch::Plane(value) => Value::Plane(value),
+            VariantDispatch::Quat(value) => Value::Quat(value),
+            VariantDispatch::Aabb(value) => Value::Aabb(value),
+            VariantDispatch::Basis(value) => Value::Basis(value),
+            VariantDispatch::Transform(value) => Value::Transform(value),
+            VariantDispatch::Color(value) => Value::Color(value),
+            VariantDispatch::NodePath(value) => {
+                Value::NodePath(value.to_godot_string().to_string())
+            }
+            VariantDispatch::Rid(_) => return Err(unsupported(VariantType::Rid)),
+            VariantDispatch::Object(_) => return Err(unsupported(VariantType::Object)),
+            VariantDispatch::Dictionary(dictionary) => Value::Dictionary(
+                dictionary
+                    .iter()
+                    .map(|(key, value)| {
+                        Ok((Value::from_variant(&key)?, Value::from_variant(&value)?))
+                    })
+                    .collect::<Result<_, FromVariantError>>()?,
+            ),
+            VariantDispatch::VariantArray(array) => Value::Array(
+                array
+                    .iter()
+                    .enumerate()
+                    .map(|(index, value)| {
+                        Value::from_variant(&value).map_err(|error| FromVariantError::InvalidItem {
+                            index,
+                            error: Box::new(error),
+                        })
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+            VariantDispatch::ByteArray(values) => Value::PoolByteArray(values.to_vec()),
+            VariantDispatch::Int32Array(values) => Value::PoolIntArray(values.to_vec()),
+            VariantDispatch::Float32Array(values) => Value::PoolRealArray(values.to_vec()),
+            VariantDispatch::StringArray(values) => {
+                Value::PoolStringArray(values.to_vec().iter().map(GodotString::to_string).collect())
+            }
+            VariantDispatch::Vector2Array(values) => Value::PoolVector2Array(values.to_vec()),
+            VariantDispatch::Vector3Array(values) => Value::PoolVector3Array(values.to_vec()),
+            VariantDispatch::ColorArray(values) => Value::PoolColorArray(values.to_vec()),
+        })
+    }
+}
+
+fn unsupported(variant_type: VariantType) -> FromVariantError {
+    FromVariantError::Custom(format!("{:?} cannot be converted to a Value", variant_type))
+}
@@ -1,5 +1,32 @@
+/// Implements methods that std has for vectors with any allocator, for
+/// `Vec<T>`, or with the `allocator_api` feature for `Vec<T, A>`.
+///
+/// The bounds on `T` go in the leading brackets.
+macro_rules! impl_vec {
+    ([$($bound:tt)*] $($body:tt)*) => {
+        #[cfg(not(feature = "allocator_api"))]
+        impl<T> Vec<T>
+        where
+            $($bound)*
+        {
+            $($body)*
+        }
+
+        #[cfg(feature = "allocator_api")]
+        impl<T, A> Vec<T, A>
+        where
+            A: std::alloc::Allocator,
+            $($bound)*
+        {
+            $($body)*
+        }
+    };
+}
+
 #[allow(clippy::module_inception)]
 mod vec;
 mod vec_impl;
+mod vec_patch;
 
 pub use vec::Vec;
+pub use vec_patch::{PatchError, VecEdit, VecPatch};
@@ -1,79 +1,292 @@
+#[cfg(feature = "allocator_api")]
+use std::alloc::{Allocator, Global};
 use std::borrow::{Borrow, BorrowMut};
 
 use gdnative::{
     core_types::VariantArray,
-    export::{Export, ExportInfo},
+    export::{hint::ArrayHint, Export, ExportInfo},
     prelude::{FromVariant, FromVariantError, OwnedToVariant, ToVariant, Variant},
 };
 
+use crate::{
+    collections::{HashMap, HashSet},
+    hint::CollectionHint,
+    with,
+};
+
+#[cfg(not(feature = "allocator_api"))]
 pub struct Vec<T> {
     pub(crate) base: std::vec::Vec<T>,
 }
 
-pub enum NoHint {}
+#[cfg(feature = "allocator_api")]
+pub struct Vec<T, A: Allocator = Global> {
+    pub(crate) base: std::vec::Vec<T, A>,
+}
 
+#[cfg(not(feature = "allocator_api"))]
 impl<T> Export for Vec<T>
 where
-    T: ToVariant,
+    T: Export,
 {
-    type Hint = NoHint;
+    type Hint = CollectionHint<T::Hint>;
 
-    fn export_info(_hint: Option<Self::Hint>) -> ExportInfo {
-        ExportInfo::new(gdnative::core_types::VariantType::VariantArray)
+    /// Exports a typed array, so the inspector uses the editor of the element
+    /// type for each element. The element hint defaults to that of `T`.
+    fn export_info(hint: Option<Self::Hint>) -> ExportInfo {
+        match hint.and_then(CollectionHint::into_element) {
+            Some(element) => ArrayHint::with_element_hint::<T>(element),
+            None => ArrayHint::with_element::<T>(),
+        }
+        .export_info()
     }
 }
 
+#[cfg(feature = "allocator_api")]
+impl<T, A> Export for Vec<T, A>
+where
+    T: Export,
+    A: Allocator,
+{
+    type Hint = CollectionHint<T::Hint>;
+
+    fn export_info(hint: Option<Self::Hint>) -> ExportInfo {
+        match hint.and_then(CollectionHint::into_element) {
+            Some(element) => ArrayHint::with_element_hint::<T>(element),
+            None => ArrayHint::with_element::<T>(),
+        }
+        .export_info()
+    }
+}
+
+#[cfg(not(feature = "allocator_api"))]
 impl<T> FromVariant for Vec<T>
 where
     T: FromVariant,
 {
     fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        Ok(Vec {
+            base: with::vec::from_variant(variant)?,
+        })
+    }
+}
+
+#[cfg(feature = "allocator_api")]
+impl<T, A> FromVariant for Vec<T, A>
+where
+    T: FromVariant,
+    A: Allocator + Default,
+{
+    /// Reads the elements into a vector allocated with `A::default()`, since
+    /// `FromVariant` has no way to pass an allocator in. Allocators without a
+    /// default, such as `&Bump`, go through [`Vec::from_variant_in`].
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        Vec::from_variant_in(variant, A::default())
+    }
+}
+
+#[cfg(feature = "allocator_api")]
+impl<T, A> Vec<T, A>
+where
+    T: FromVariant,
+    A: Allocator,
+{
+    /// Reads a `Variant` holding an `Array` into a vector allocated with
+    /// `alloc`.
+    pub fn from_variant_in(variant: &Variant, alloc: A) -> Result<Vec<T, A>, FromVariantError> {
         let array = VariantArray::from_variant(variant)?;
-        let mut vec: Vec<T> = Vec::with_capacity(array.len() as usize);
+        let mut base = std::vec::Vec::with_capacity_in(array.len() as usize, alloc);
         for variant in array.iter() {
-            let value = T::from_variant(&variant)?;
-            vec.push(value);
+            base.push(T::from_variant(&variant)?);
         }
-        Ok(vec)
+        Ok(Vec { base })
     }
 }
 
+#[cfg(not(feature = "allocator_api"))]
 impl<T> ToVariant for Vec<T>
 where
     T: ToVariant,
 {
     fn to_variant(&self) -> Variant {
+        with::vec::to_variant(&self.base)
+    }
+}
+
+#[cfg(feature = "allocator_api")]
+impl<T, A> ToVariant for Vec<T, A>
+where
+    T: ToVariant,
+    A: Allocator,
+{
+    fn to_variant(&self) -> Variant {
+        with::vec::to_variant(&self.base)
+    }
+}
+
+impl_vec! {
+    [T: OwnedToVariant]
+
+    /// Converts the vector into a `Variant` holding an `Array`, moving each
+    /// element into it instead of borrowing.
+    pub fn into_variant(self) -> Variant {
         let array = VariantArray::new();
-        for value in &self.base {
-            array.push(value.to_variant());
+        for value in self.base {
+            array.push(value);
         }
         array.owned_to_variant()
     }
 }
 
+#[cfg(not(feature = "allocator_api"))]
 impl<T> Borrow<std::vec::Vec<T>> for Vec<T> {
     fn borrow(&self) -> &std::vec::Vec<T> {
         &self.base
     }
 }
 
+#[cfg(feature = "allocator_api")]
+impl<T, A: Allocator> Borrow<std::vec::Vec<T, A>> for Vec<T, A> {
+    fn borrow(&self) -> &std::vec::Vec<T, A> {
+        &self.base
+    }
+}
+
+#[cfg(not(feature = "allocator_api"))]
 impl<T> BorrowMut<std::vec::Vec<T>> for Vec<T> {
     fn borrow_mut(&mut self) -> &mut std::vec::Vec<T> {
         &mut self.base
     }
 }
 
+#[cfg(feature = "allocator_api")]
+impl<T, A: Allocator> BorrowMut<std::vec::Vec<T, A>> for Vec<T, A> {
+    fn borrow_mut(&mut self) -> &mut std::vec::Vec<T, A> {
+        &mut self.base
+    }
+}
+
+#[cfg(not(feature = "allocator_api"))]
 impl<T> From<std::vec::Vec<T>> for Vec<T> {
     fn from(vec: std::vec::Vec<T>) -> Vec<T> {
         Vec { base: vec }
     }
 }
 
+#[cfg(feature = "allocator_api")]
+impl<T, A: Allocator> From<std::vec::Vec<T, A>> for Vec<T, A> {
+    fn from(vec: std::vec::Vec<T, A>) -> Vec<T, A> {
+        Vec { base: vec }
+    }
+}
+
+#[cfg(not(feature = "allocator_api"))]
+impl<T> Vec<T> {
+    /// Returns the std vector this vector wraps, without copying.
+    #[inline]
+    pub fn into_inner(self) -> std::vec::Vec<T> {
+        self.base
+    }
+}
+
+#[cfg(feature = "allocator_api")]
+impl<T, A: Allocator> Vec<T, A> {
+    /// Returns the std vector this vector wraps, without copying.
+    #[inline]
+    pub fn into_inner(self) -> std::vec::Vec<T, A> {
+        self.base
+    }
+}
+
+#[cfg(not(feature = "allocator_api"))]
+impl<T> AsRef<std::vec::Vec<T>> for Vec<T> {
+    fn as_ref(&self) -> &std::vec::Vec<T> {
+        &self.base
+    }
+}
+
+#[cfg(feature = "allocator_api")]
+impl<T, A: Allocator> AsRef<std::vec::Vec<T, A>> for Vec<T, A> {
+    fn as_ref(&self) -> &std::vec::Vec<T, A> {
+        &self.base
+    }
+}
+
+#[cfg(not(feature = "allocator_api"))]
+impl<T> AsMut<std::vec::Vec<T>> for Vec<T> {
+    fn as_mut(&mut self) -> &mut std::vec::Vec<T> {
+        &mut self.base
+    }
+}
+
+#[cfg(feature = "allocator_api")]
+impl<T, A: Allocator> AsMut<std::vec::Vec<T, A>> for Vec<T, A> {
+    fn as_mut(&mut self) -> &mut std::vec::Vec<T, A> {
+        &mut self.base
+    }
+}
+
+#[cfg(not(feature = "allocator_api"))]
+impl<T> From<Vec<T>> for std::vec::Vec<T> {
+    fn from(vec: Vec<T>) -> std::vec::Vec<T> {
+        vec.base
+    }
+}
+
+#[cfg(feature = "allocator_api")]
+impl<T, A: Allocator> From<Vec<T, A>> for std::vec::Vec<T, A> {
+    fn from(vec: Vec<T, A>) -> std::vec::Vec<T, A> {
+        vec.base
+    }
+}
+
+/// Collects the elements of the set, in the order the set iterates them.
+impl<T, S> From<HashSet<T, S>> for Vec<T> {
+    fn from(set: HashSet<T, S>) -> Vec<T> {
+        Vec {
+            base: set.base.into_iter().collect(),
+        }
+    }
+}
+
+/// Collects the entries of the map as pairs, in the order the map iterates
+/// them.
+impl<K, V, S> From<HashMap<K, V, S>> for Vec<(K, V)> {
+    fn from(map: HashMap<K, V, S>) -> Vec<(K, V)> {
+        Vec {
+            base: map.base.into_iter().collect(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::borrow::{Borrow, BorrowMut};
 
-    use crate::vec::Vec;
+    use gdnative::prelude::FromVariant;
+
+    use crate::{
+        collections::{HashMap, HashSet},
+        vec::Vec,
+        with,
+    };
+
+    #[test]
+    #[ignore = "creating a Variant needs the Godot engine"]
+    fn test_into_variant() {
+        let vec = Vec::from(vec![1, 2, 3]);
+        let expected = with::vec::to_variant(&vec);
+        let variant = vec.into_variant();
+
+        assert_eq!(
+            Vec::<i64>::from_variant(&variant),
+            Vec::<i64>::from_variant(&expected)
+        );
+        assert_eq!(
+            Vec::<i64>::from_variant(&variant),
+            Ok(Vec::from(vec![1, 2, 3]))
+        );
+    }
 
     #[test]
     fn test_borrow() {
@@ -88,4 +301,29 @@ mod tests {
         let std: &std::vec::Vec<i32> = vec.borrow_mut();
         assert_eq!(std.len(), 3);
     }
+
+    #[test]
+    fn test_into_inner() {
+        let vec: Vec<i32> = Vec::from(vec![2, 1, 3]);
+        let std: std::vec::Vec<i32> = vec.clone().into_inner();
+        assert_eq!(std, vec);
+        assert_eq!(std::vec::Vec::from(vec), std);
+    }
+
+    #[test]
+    fn test_as_ref() {
+        let mut vec: Vec<i32> = Vec::from(vec![2, 1]);
+        AsMut::<std::vec::Vec<i32>>::as_mut(&mut vec).push(3);
+        let std: &std::vec::Vec<i32> = vec.as_ref();
+        assert_eq!(std, &[2, 1, 3]);
+    }
+
+    #[test]
+    fn test_from_collections() {
+        let vec = Vec::from(HashSet::from([7]));
+        assert_eq!(vec, [7]);
+
+        let vec = Vec::from(HashMap::from([(1, "a")]));
+        assert_eq!(vec, [(1, "a")]);
+    }
 }
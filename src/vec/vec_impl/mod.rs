@@ -1,5 +1,7 @@
 #[allow(clippy::module_inception)]
 mod vec_impl;
+#[cfg(feature = "allocator_api")]
+mod vec_impl_allocator;
 mod vec_impl_as_mut;
 mod vec_impl_as_ref;
 mod vec_impl_borrow;
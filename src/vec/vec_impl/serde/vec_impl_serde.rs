@@ -1,7 +1,13 @@
+#[cfg(feature = "allocator_api")]
+use std::{alloc::Allocator, fmt, marker::PhantomData};
+
+#[cfg(feature = "allocator_api")]
+use serde::de::{SeqAccess, Visitor};
 use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::vec::Vec;
 
+#[cfg(not(feature = "allocator_api"))]
 impl<'de, T> Deserialize<'de> for Vec<T>
 where
     T: Deserialize<'de>,
@@ -16,6 +22,48 @@ where
     }
 }
 
+/// Reads the elements into a vector allocated with `A::default()`, since
+/// `Deserialize` has no way to pass an allocator in.
+#[cfg(feature = "allocator_api")]
+impl<'de, T, A> Deserialize<'de> for Vec<T, A>
+where
+    T: Deserialize<'de>,
+    A: Allocator + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct VecVisitor<T, A>(PhantomData<(T, A)>);
+
+        impl<'de, T, A> Visitor<'de> for VecVisitor<T, A>
+        where
+            T: Deserialize<'de>,
+            A: Allocator + Default,
+        {
+            type Value = Vec<T, A>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a sequence")
+            }
+
+            fn visit_seq<S>(self, mut seq: S) -> Result<Vec<T, A>, S::Error>
+            where
+                S: SeqAccess<'de>,
+            {
+                let mut vec = Vec::new_in(A::default());
+                while let Some(value) = seq.next_element()? {
+                    vec.push(value);
+                }
+                Ok(vec)
+            }
+        }
+
+        deserializer.deserialize_seq(VecVisitor(PhantomData))
+    }
+}
+
+#[cfg(not(feature = "allocator_api"))]
 impl<T> Serialize for Vec<T>
 where
     T: Serialize,
@@ -28,6 +76,20 @@ where
     }
 }
 
+#[cfg(feature = "allocator_api")]
+impl<T, A> Serialize for Vec<T, A>
+where
+    T: Serialize,
+    A: Allocator,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.base.as_slice().serialize(serializer)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::vec::Vec;
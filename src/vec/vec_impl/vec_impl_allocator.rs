@@ -0,0 +1,126 @@
+use std::alloc::Allocator;
+
+use crate::vec::Vec;
+
+impl<T, A> Vec<T, A>
+where
+    A: Allocator,
+{
+    /// Constructs a new, empty `Vec<T, A>` that allocates with `alloc`.
+    ///
+    /// The vector will not allocate until elements are pushed onto it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::System;
+    ///
+    /// use gdvariants::vec::Vec;
+    ///
+    /// let mut vec: Vec<i32, _> = Vec::new_in(System);
+    /// vec.push(1);
+    /// assert_eq!(*vec, [1]);
+    /// ```
+    #[inline]
+    pub const fn new_in(alloc: A) -> Vec<T, A> {
+        Vec {
+            base: std::vec::Vec::new_in(alloc),
+        }
+    }
+
+    /// Constructs a new, empty `Vec<T, A>` with the specified capacity that
+    /// allocates with `alloc`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::System;
+    ///
+    /// use gdvariants::vec::Vec;
+    ///
+    /// let vec: Vec<i32, _> = Vec::with_capacity_in(10, System);
+    /// assert_eq!(vec.len(), 0);
+    /// assert!(vec.capacity() >= 10);
+    /// ```
+    #[inline]
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Vec<T, A> {
+        Vec {
+            base: std::vec::Vec::with_capacity_in(capacity, alloc),
+        }
+    }
+
+    /// Returns a reference to the underlying allocator.
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        self.base.allocator()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::alloc::System;
+
+    use crate::vec::Vec;
+
+    #[test]
+    fn test_new_in() {
+        let mut vec: Vec<i32, System> = Vec::new_in(System);
+        vec.push(2);
+        vec.extend_from_slice(&[1, 3]);
+
+        assert_eq!(*vec, [2, 1, 3]);
+        assert_eq!(format!("{:?}", vec), "[2, 1, 3]");
+    }
+
+    #[test]
+    fn test_with_capacity_in() {
+        let vec: Vec<i32, System> = Vec::with_capacity_in(4, System);
+
+        assert!(vec.capacity() >= 4);
+        let _: &System = vec.allocator();
+    }
+
+    #[test]
+    fn test_from_std() {
+        let mut std = std::vec::Vec::new_in(System);
+        std.push(1);
+        let vec = Vec::from(std);
+
+        assert_eq!(vec.len(), 1);
+    }
+
+    #[test]
+    fn test_traits_any_allocator() {
+        // `&System` has no `Default`, like arena allocators.
+        let mut vec: Vec<i32, &System> = Vec::new_in(&System);
+        vec.extend([2, 1]);
+        vec.extend(&[3]);
+        let mut other: Vec<i32, System> = Vec::new_in(System);
+        other.extend([2, 1, 3]);
+        let mut larger: Vec<i32, &System> = Vec::new_in(&System);
+        larger.push(3);
+
+        assert_eq!(vec, other);
+        assert_eq!(vec[1..], [1, 3]);
+        assert!(vec.clone() < larger);
+        assert_eq!((&vec).into_iter().sum::<i32>(), 6);
+        assert_eq!(vec.into_iter().collect::<std::vec::Vec<_>>(), [2, 1, 3]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let vec: Vec<i32, System> = serde_json::from_str("[2, 1, 3]").unwrap();
+
+        assert_eq!(vec, [2, 1, 3]);
+        assert_eq!(serde_json::to_string(&vec).unwrap(), "[2,1,3]");
+    }
+}
@@ -1,5 +1,6 @@
 use crate::vec::Vec;
 
+#[cfg(not(feature = "allocator_api"))]
 impl<T> Clone for Vec<T>
 where
     T: Clone,
@@ -15,6 +16,23 @@ where
     }
 }
 
+#[cfg(feature = "allocator_api")]
+impl<T, A> Clone for Vec<T, A>
+where
+    T: Clone,
+    A: std::alloc::Allocator + Clone,
+{
+    fn clone(&self) -> Vec<T, A> {
+        Vec {
+            base: self.base.clone(),
+        }
+    }
+
+    fn clone_from(&mut self, other: &Vec<T, A>) {
+        self.base.clone_from(&other.base)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::vec::Vec;
@@ -1,6 +1,7 @@
 use crate::vec::Vec;
 use std::fmt::{Debug, Error, Formatter};
 
+#[cfg(not(feature = "allocator_api"))]
 impl<T> Debug for Vec<T>
 where
     T: Debug,
@@ -10,6 +11,17 @@ where
     }
 }
 
+#[cfg(feature = "allocator_api")]
+impl<T, A> Debug for Vec<T, A>
+where
+    T: Debug,
+    A: std::alloc::Allocator,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        self.base.fmt(f)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::vec::Vec;
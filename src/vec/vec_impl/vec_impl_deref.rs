@@ -1,6 +1,7 @@
 use crate::vec::Vec;
 use std::ops::Deref;
 
+#[cfg(not(feature = "allocator_api"))]
 impl<T> Deref for Vec<T> {
     type Target = [T];
 
@@ -9,6 +10,15 @@ impl<T> Deref for Vec<T> {
     }
 }
 
+#[cfg(feature = "allocator_api")]
+impl<T, A: std::alloc::Allocator> Deref for Vec<T, A> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.base.deref()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::ops::Deref;
@@ -1,12 +1,20 @@
 use crate::vec::Vec;
 use std::ops::DerefMut;
 
+#[cfg(not(feature = "allocator_api"))]
 impl<T> DerefMut for Vec<T> {
     fn deref_mut(&mut self) -> &mut [T] {
         self.base.deref_mut()
     }
 }
 
+#[cfg(feature = "allocator_api")]
+impl<T, A: std::alloc::Allocator> DerefMut for Vec<T, A> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.base.deref_mut()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::ops::DerefMut;
@@ -1,3 +1,12 @@
 use crate::vec::Vec;
 
+#[cfg(not(feature = "allocator_api"))]
 impl<T> Eq for Vec<T> where T: Eq {}
+
+#[cfg(feature = "allocator_api")]
+impl<T, A> Eq for Vec<T, A>
+where
+    T: Eq,
+    A: std::alloc::Allocator,
+{
+}
@@ -1,5 +1,6 @@
 use crate::vec::Vec;
 
+#[cfg(not(feature = "allocator_api"))]
 impl<T> Extend<T> for Vec<T> {
     #[inline]
     fn extend<I>(&mut self, iter: I)
@@ -10,12 +11,24 @@ impl<T> Extend<T> for Vec<T> {
     }
 }
 
+#[cfg(feature = "allocator_api")]
+impl<T, A: std::alloc::Allocator> Extend<T> for Vec<T, A> {
+    #[inline]
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.base.extend(iter)
+    }
+}
+
 /// Extend implementation that copies elements out of references before pushing them onto the Vec.
 ///
 /// This implementation is specialized for slice iterators, where it uses [`copy_from_slice`] to
 /// append the entire slice at once.
 ///
 /// [`copy_from_slice`]: slice::copy_from_slice
+#[cfg(not(feature = "allocator_api"))]
 impl<'a, T> Extend<&'a T> for Vec<T>
 where
     T: 'a + Copy,
@@ -28,6 +41,21 @@ where
     }
 }
 
+/// Extend implementation that copies elements out of references before pushing them onto the Vec.
+#[cfg(feature = "allocator_api")]
+impl<'a, T, A> Extend<&'a T> for Vec<T, A>
+where
+    T: 'a + Copy,
+    A: std::alloc::Allocator,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.base.extend(iter)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::vec::Vec;
@@ -1,6 +1,7 @@
 use crate::vec::Vec;
 use std::hash::{Hash, Hasher};
 
+#[cfg(not(feature = "allocator_api"))]
 impl<T> Hash for Vec<T>
 where
     T: Hash,
@@ -11,6 +12,18 @@ where
     }
 }
 
+#[cfg(feature = "allocator_api")]
+impl<T, A> Hash for Vec<T, A>
+where
+    T: Hash,
+    A: std::alloc::Allocator,
+{
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.base.hash(state)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{
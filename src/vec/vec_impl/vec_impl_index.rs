@@ -2,6 +2,7 @@ use std::{ops::Index, slice::SliceIndex};
 
 use crate::vec::Vec;
 
+#[cfg(not(feature = "allocator_api"))]
 impl<T, I> Index<I> for Vec<T>
 where
     I: SliceIndex<[T]>,
@@ -13,6 +14,19 @@ where
     }
 }
 
+#[cfg(feature = "allocator_api")]
+impl<T, I, A> Index<I> for Vec<T, A>
+where
+    I: SliceIndex<[T]>,
+    A: std::alloc::Allocator,
+{
+    type Output = <I as SliceIndex<[T]>>::Output;
+
+    fn index(&self, index: I) -> &<Vec<T, A> as Index<I>>::Output {
+        self.base.index(index)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::ops::Index;
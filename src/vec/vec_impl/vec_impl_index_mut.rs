@@ -5,6 +5,7 @@ use std::{
 
 use crate::vec::Vec;
 
+#[cfg(not(feature = "allocator_api"))]
 impl<T, I> IndexMut<I> for Vec<T>
 where
     I: SliceIndex<[T]>,
@@ -15,6 +16,18 @@ where
     }
 }
 
+#[cfg(feature = "allocator_api")]
+impl<T, I, A> IndexMut<I> for Vec<T, A>
+where
+    I: SliceIndex<[T]>,
+    A: std::alloc::Allocator,
+{
+    #[inline]
+    fn index_mut(&mut self, index: I) -> &mut <Vec<T, A> as Index<I>>::Output {
+        self.base.index_mut(index)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::ops::IndexMut;
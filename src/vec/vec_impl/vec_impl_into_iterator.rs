@@ -1,10 +1,13 @@
 use crate::vec::Vec;
 
+#[cfg(feature = "allocator_api")]
+use std::alloc::Allocator;
 use std::{
     slice::{self, IterMut},
     vec::IntoIter,
 };
 
+#[cfg(not(feature = "allocator_api"))]
 impl<T> IntoIterator for Vec<T> {
     type Item = T;
     type IntoIter = IntoIter<T>;
@@ -28,6 +31,7 @@ impl<T> IntoIterator for Vec<T> {
     }
 }
 
+#[cfg(not(feature = "allocator_api"))]
 impl<'a, T> IntoIterator for &'a Vec<T> {
     type Item = &'a T;
     type IntoIter = slice::Iter<'a, T>;
@@ -37,6 +41,7 @@ impl<'a, T> IntoIterator for &'a Vec<T> {
     }
 }
 
+#[cfg(not(feature = "allocator_api"))]
 impl<'a, T> IntoIterator for &'a mut Vec<T> {
     type Item = &'a mut T;
     type IntoIter = IterMut<'a, T>;
@@ -46,6 +51,40 @@ impl<'a, T> IntoIterator for &'a mut Vec<T> {
     }
 }
 
+#[cfg(feature = "allocator_api")]
+impl<T, A: Allocator> IntoIterator for Vec<T, A> {
+    type Item = T;
+    type IntoIter = IntoIter<T, A>;
+
+    /// Creates a consuming iterator, that is, one that moves each value out of
+    /// the vector (from start to end). The vector cannot be used after calling
+    /// this.
+    #[inline]
+    fn into_iter(self) -> IntoIter<T, A> {
+        self.base.into_iter()
+    }
+}
+
+#[cfg(feature = "allocator_api")]
+impl<'a, T, A: Allocator> IntoIterator for &'a Vec<T, A> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> slice::Iter<'a, T> {
+        self.base.iter()
+    }
+}
+
+#[cfg(feature = "allocator_api")]
+impl<'a, T, A: Allocator> IntoIterator for &'a mut Vec<T, A> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.base.iter_mut()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::vec::Vec;
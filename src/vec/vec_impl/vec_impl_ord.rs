@@ -3,6 +3,7 @@ use std::cmp::Ordering;
 use crate::vec::Vec;
 
 /// Implements ordering of vectors, [lexicographically](core::cmp::Ord#lexicographical-comparison).
+#[cfg(not(feature = "allocator_api"))]
 impl<T> Ord for Vec<T>
 where
     T: Ord,
@@ -13,6 +14,19 @@ where
     }
 }
 
+/// Implements ordering of vectors, [lexicographically](core::cmp::Ord#lexicographical-comparison).
+#[cfg(feature = "allocator_api")]
+impl<T, A> Ord for Vec<T, A>
+where
+    T: Ord,
+    A: std::alloc::Allocator,
+{
+    #[inline]
+    fn cmp(&self, other: &Vec<T, A>) -> Ordering {
+        self.base.cmp(&other.base)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::vec::Vec;
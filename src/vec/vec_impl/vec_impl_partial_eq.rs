@@ -1,5 +1,3 @@
-use crate::vec::Vec;
-
 macro_rules! __impl_slice_eq {
     ([$($vars:tt)*] $lhs:ty, $rhs:ty $(where $ty:ty: $bound:ident)?) => {
         impl<T, U, $($vars)*> PartialEq<$rhs> for $lhs
@@ -13,14 +11,38 @@ macro_rules! __impl_slice_eq {
     }
 }
 
-__impl_slice_eq! { [] Vec<T>, Vec<U> }
-__impl_slice_eq! { [] Vec<T>, &[U] }
-__impl_slice_eq! { [] Vec<T>, &mut [U] }
-__impl_slice_eq! { [] Vec<T>, std::vec::Vec<U> }
-__impl_slice_eq! { [] &[T], Vec<U> }
-__impl_slice_eq! { [] &mut [T], Vec<U> }
-__impl_slice_eq! { [] Vec<T>, [U] }
-__impl_slice_eq! { [] [T], Vec<U> }
-__impl_slice_eq! { [] std::vec::Vec<T>, Vec<U> }
-__impl_slice_eq! { [const N: usize] Vec<T>, [U; N] }
-__impl_slice_eq! { [const N: usize] Vec<T>, &[U; N] }
+#[cfg(not(feature = "allocator_api"))]
+mod global {
+    use crate::vec::Vec;
+
+    __impl_slice_eq! { [] Vec<T>, Vec<U> }
+    __impl_slice_eq! { [] Vec<T>, &[U] }
+    __impl_slice_eq! { [] Vec<T>, &mut [U] }
+    __impl_slice_eq! { [] Vec<T>, std::vec::Vec<U> }
+    __impl_slice_eq! { [] &[T], Vec<U> }
+    __impl_slice_eq! { [] &mut [T], Vec<U> }
+    __impl_slice_eq! { [] Vec<T>, [U] }
+    __impl_slice_eq! { [] [T], Vec<U> }
+    __impl_slice_eq! { [] std::vec::Vec<T>, Vec<U> }
+    __impl_slice_eq! { [const N: usize] Vec<T>, [U; N] }
+    __impl_slice_eq! { [const N: usize] Vec<T>, &[U; N] }
+}
+
+#[cfg(feature = "allocator_api")]
+mod allocator {
+    use std::alloc::Allocator;
+
+    use crate::vec::Vec;
+
+    __impl_slice_eq! { [A1: Allocator, A2: Allocator] Vec<T, A1>, Vec<U, A2> }
+    __impl_slice_eq! { [A: Allocator] Vec<T, A>, &[U] }
+    __impl_slice_eq! { [A: Allocator] Vec<T, A>, &mut [U] }
+    __impl_slice_eq! { [A1: Allocator, A2: Allocator] Vec<T, A1>, std::vec::Vec<U, A2> }
+    __impl_slice_eq! { [A: Allocator] &[T], Vec<U, A> }
+    __impl_slice_eq! { [A: Allocator] &mut [T], Vec<U, A> }
+    __impl_slice_eq! { [A: Allocator] Vec<T, A>, [U] }
+    __impl_slice_eq! { [A: Allocator] [T], Vec<U, A> }
+    __impl_slice_eq! { [A1: Allocator, A2: Allocator] std::vec::Vec<T, A1>, Vec<U, A2> }
+    __impl_slice_eq! { [A: Allocator, const N: usize] Vec<T, A>, [U; N] }
+    __impl_slice_eq! { [A: Allocator, const N: usize] Vec<T, A>, &[U; N] }
+}
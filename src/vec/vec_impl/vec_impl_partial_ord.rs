@@ -3,6 +3,7 @@ use std::cmp::Ordering;
 use crate::vec::Vec;
 
 /// Implements comparison of vectors, [lexicographically](core::cmp::Ord#lexicographical-comparison).
+#[cfg(not(feature = "allocator_api"))]
 impl<T> PartialOrd for Vec<T>
 where
     T: PartialOrd,
@@ -13,6 +14,19 @@ where
     }
 }
 
+/// Implements comparison of vectors, [lexicographically](core::cmp::Ord#lexicographical-comparison).
+#[cfg(feature = "allocator_api")]
+impl<T, A> PartialOrd for Vec<T, A>
+where
+    T: PartialOrd,
+    A: std::alloc::Allocator,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.base.partial_cmp(&other.base)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::vec::Vec;
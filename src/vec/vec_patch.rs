@@ -0,0 +1,605 @@
+use std::{error, fmt};
+
+use gdnative::{
+    core_types::VariantArray,
+    prelude::{FromVariant, FromVariantError, OwnedToVariant, ToVariant, Variant},
+};
+#[cfg(feature = "serde")]
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::vec::Vec;
+
+/// One run of changes in a [`VecPatch`].
+///
+/// Indices are positions in the vector at the time the edit is applied, after
+/// the edits before it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VecEdit<T> {
+    /// Inserts `values` before `index`.
+    Insert { index: usize, values: Vec<T> },
+    /// Removes `len` elements starting at `index`.
+    Remove { index: usize, len: usize },
+    /// Overwrites the elements starting at `index` with `values`.
+    Replace { index: usize, values: Vec<T> },
+}
+
+impl<T> VecEdit<T> {
+    /// Returns the length of a vector of length `len` after the edit, or
+    /// `None` if the edit does not fit.
+    fn apply_len(&self, len: usize) -> Option<usize> {
+        let (index, removed, values) = self.splice();
+        if index.checked_add(removed)? > len {
+            return None;
+        }
+        (len - removed).checked_add(values.len())
+    }
+
+    /// Returns the edit as `(index, removed, inserted)`, which is how it is
+    /// encoded.
+    fn splice(&self) -> (usize, usize, &[T]) {
+        match self {
+            VecEdit::Insert { index, values } => (*index, 0, values),
+            VecEdit::Remove { index, len } => (*index, *len, &[]),
+            VecEdit::Replace { index, values } => (*index, values.len(), values),
+        }
+    }
+
+    fn from_splice(index: usize, removed: usize, values: Vec<T>) -> Result<VecEdit<T>, String> {
+        if removed == 0 {
+            Ok(VecEdit::Insert { index, values })
+        } else if values.is_empty() {
+            Ok(VecEdit::Remove {
+                index,
+                len: removed,
+            })
+        } else if removed == values.len() {
+            Ok(VecEdit::Replace { index, values })
+        } else {
+            Err(format!(
+                "edit at {} removes {} elements but inserts {}",
+                index,
+                removed,
+                values.len()
+            ))
+        }
+    }
+}
+
+/// The edits that turn one state of a [`Vec`] into another.
+///
+/// Created with [`Vec::diff`] and replayed with [`Vec::apply`]. The edits
+/// are a shortest edit script: unchanged elements are never included, while
+/// elements that moved are removed and inserted again.
+///
+/// Converts to a `Variant` holding an `Array` with one
+/// `[index, removed, inserted]` `Array` per edit, where `inserted` is an
+/// `Array` of elements. Serde uses the same tuples.
+///
+/// # Examples
+///
+/// ```
+/// use gdvariants::vec::{Vec, VecEdit};
+///
+/// let old = Vec::from(vec!["hi", "gg", "brb"]);
+/// let new = Vec::from(vec!["hi", "brb", "back"]);
+///
+/// let patch = new.diff(&old);
+/// assert_eq!(
+///     patch.edits(),
+///     [
+///         VecEdit::Remove { index: 1, len: 1 },
+///         VecEdit::Insert { index: 2, values: Vec::from(vec!["back"]) },
+///     ]
+/// );
+///
+/// let mut replica = old.clone();
+/// replica.apply(&patch);
+/// assert_eq!(replica, new);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VecPatch<T> {
+    edits: Vec<VecEdit<T>>,
+}
+
+impl<T> VecPatch<T> {
+    /// Creates a patch that changes nothing.
+    #[inline]
+    #[must_use]
+    pub fn new() -> VecPatch<T> {
+        VecPatch { edits: Vec::new() }
+    }
+
+    /// Returns the edits, in the order they are applied.
+    #[inline]
+    pub fn edits(&self) -> &[VecEdit<T>] {
+        &self.edits
+    }
+
+    /// Returns `true` if the patch changes nothing.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+}
+
+impl<T> Default for VecPatch<T> {
+    #[inline]
+    fn default() -> VecPatch<T> {
+        VecPatch::new()
+    }
+}
+
+impl<T> From<std::vec::Vec<VecEdit<T>>> for VecPatch<T> {
+    fn from(edits: std::vec::Vec<VecEdit<T>>) -> VecPatch<T> {
+        VecPatch {
+            edits: Vec::from(edits),
+        }
+    }
+}
+
+/// Error returned by [`Vec::try_apply`] when an edit reaches past the end
+/// of the vector.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PatchError {
+    /// The position of the edit in the patch.
+    pub edit: usize,
+    /// The length of the vector before the edit.
+    pub len: usize,
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "edit {} does not fit a vector of length {}",
+            self.edit, self.len
+        )
+    }
+}
+
+impl error::Error for PatchError {}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Keep,
+    Remove,
+    Insert,
+}
+
+/// Returns the shortest edit script from `old` to `new`, one op per element,
+/// with the Myers algorithm.
+fn shortest_edit<T: PartialEq>(old: &[T], new: &[T]) -> std::vec::Vec<Op> {
+    let n = old.len() as isize;
+    let m = new.len() as isize;
+    let max = n + m;
+
+    // `v[k + max]` is the furthest `x` reached on diagonal `k = x - y`. Only
+    // diagonals `-d..=d` are kept for each `d`, for the backtrack.
+    let mut v = vec![0isize; 2 * max as usize + 2];
+    let mut trace = std::vec::Vec::new();
+    'search: for d in 0..=max {
+        trace.push(v[(max - d) as usize..=(max + d) as usize].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let i = (k + max) as usize;
+            let mut x = if k == -d || (k != d && v[i - 1] < v[i + 1]) {
+                v[i + 1]
+            } else {
+                v[i - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[i] = x;
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    let mut ops = std::vec::Vec::with_capacity((n + m) as usize);
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        // `v` holds diagonals `-d..=d`, from before step `d`.
+        let at = |k: isize| v[(k + d) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = if d == 0 { 0 } else { at(prev_k) };
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y {
+            ops.push(Op::Keep);
+            x -= 1;
+            y -= 1;
+        }
+        if d > 0 {
+            ops.push(if x == prev_x { Op::Insert } else { Op::Remove });
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    ops.reverse();
+    ops
+}
+
+impl<T> Vec<T>
+where
+    T: PartialEq + Clone,
+{
+    /// Returns the edits that turn `old` into this vector.
+    ///
+    /// Takes `O((n + m) d)` time, where `d` is the number of elements
+    /// removed and inserted, after skipping the common prefix and suffix.
+    pub fn diff(&self, old: &[T]) -> VecPatch<T> {
+        let new = self.as_slice();
+        let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(new[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let old = &old[prefix..old.len() - suffix];
+        let new = &new[prefix..new.len() - suffix];
+
+        let mut edits = std::vec::Vec::new();
+        let ops = shortest_edit(old, new);
+        let (mut ops, mut x, mut y) = (ops.as_slice(), 0, 0);
+        while !ops.is_empty() {
+            let kept = ops.iter().take_while(|op| **op == Op::Keep).count();
+            x += kept;
+            y += kept;
+            ops = &ops[kept..];
+
+            let run = ops.iter().take_while(|op| **op != Op::Keep).count();
+            let removed = ops[..run].iter().filter(|op| **op == Op::Remove).count();
+            let inserted = run - removed;
+            ops = &ops[run..];
+            if run == 0 {
+                continue;
+            }
+
+            // `y` is where the run starts in the patched vector.
+            let index = prefix + y;
+            let replaced = removed.min(inserted);
+            if replaced > 0 {
+                edits.push(VecEdit::Replace {
+                    index,
+                    values: Vec::from(new[y..y + replaced].to_vec()),
+                });
+            }
+            if removed > replaced {
+                edits.push(VecEdit::Remove {
+                    index: index + replaced,
+                    len: removed - replaced,
+                });
+            }
+            if inserted > replaced {
+                edits.push(VecEdit::Insert {
+                    index: index + replaced,
+                    values: Vec::from(new[y + replaced..y + inserted].to_vec()),
+                });
+            }
+            x += removed;
+            y += inserted;
+        }
+        debug_assert_eq!((x, y), (old.len(), new.len()));
+        VecPatch::from(edits)
+    }
+
+    /// Applies the edits of a patch created with [`diff`](Self::diff).
+    ///
+    /// Applying `new.diff(&old)` to `old` makes it equal to `new`. Only use
+    /// it for patches from a trusted source, and [`try_apply`](Self::try_apply)
+    /// for patches received from peers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an edit reaches past the end of the vector, which happens
+    /// when the patch was made against a different vector.
+    pub fn apply(&mut self, patch: &VecPatch<T>) {
+        if let Err(error) = self.try_apply(patch) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Applies the edits of a patch, or fails without changing the vector if
+    /// an edit reaches past its end.
+    pub fn try_apply(&mut self, patch: &VecPatch<T>) -> Result<(), PatchError> {
+        let mut len = self.len();
+        for (position, edit) in patch.edits.iter().enumerate() {
+            len = edit.apply_len(len).ok_or(PatchError {
+                edit: position,
+                len,
+            })?;
+        }
+
+        for edit in patch.edits.iter() {
+            match edit {
+                VecEdit::Insert { index, values } => {
+                    self.base.splice(*index..*index, values.iter().cloned());
+                }
+                VecEdit::Remove { index, len } => {
+                    self.base.drain(*index..*index + *len);
+                }
+                VecEdit::Replace { index, values } => {
+                    self.base[*index..*index + values.len()].clone_from_slice(values);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T> FromVariant for VecPatch<T>
+where
+    T: FromVariant,
+{
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        let array = VariantArray::from_variant(variant)?;
+        let mut edits = std::vec::Vec::with_capacity(array.len() as usize);
+        for variant in array.iter() {
+            let edit = VariantArray::from_variant(&variant)?;
+            if edit.len() != 3 {
+                return Err(FromVariantError::InvalidLength {
+                    len: edit.len() as usize,
+                    expected: 3,
+                });
+            }
+            let count = |variant: &Variant| {
+                let count = i64::from_variant(variant)?;
+                usize::try_from(count)
+                    .map_err(|_| FromVariantError::Custom(format!("invalid count {}", count)))
+            };
+            let index = count(&edit.get(0))?;
+            let removed = count(&edit.get(1))?;
+            let values = Vec::from_variant(&edit.get(2))?;
+            edits.push(
+                VecEdit::from_splice(index, removed, values).map_err(FromVariantError::Custom)?,
+            );
+        }
+        Ok(VecPatch::from(edits))
+    }
+}
+
+impl<T> ToVariant for VecPatch<T>
+where
+    T: ToVariant,
+{
+    fn to_variant(&self) -> Variant {
+        let array = VariantArray::new();
+        for edit in self.edits.iter() {
+            let (index, removed, values) = edit.splice();
+            let splice = VariantArray::new();
+            splice.push(index as i64);
+            splice.push(removed as i64);
+            splice.push(crate::with::vec::to_variant(values));
+            array.push(splice.owned_to_variant());
+        }
+        array.owned_to_variant()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> Serialize for VecPatch<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.edits.iter().map(VecEdit::splice))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> Deserialize<'de> for VecPatch<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let splices: std::vec::Vec<(usize, usize, Vec<T>)> =
+            Deserialize::deserialize(deserializer)?;
+        let edits = splices
+            .into_iter()
+            .map(|(index, removed, values)| VecEdit::from_splice(index, removed, values))
+            .collect::<Result<std::vec::Vec<_>, _>>()
+            .map_err(D::Error::custom)?;
+        Ok(VecPatch::from(edits))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test_util::Rng,
+        vec::{PatchError, Vec, VecEdit, VecPatch},
+    };
+
+    fn sequence(rng: &mut Rng) -> Vec<u8> {
+        let len = rng.next(20);
+        (0..len).map(|_| rng.next(4) as u8).collect()
+    }
+
+    fn round_trip<T: PartialEq + Clone + std::fmt::Debug>(old: &Vec<T>, new: &Vec<T>) {
+        let patch = new.diff(old);
+        let mut vec = old.clone();
+        vec.apply(&patch);
+
+        assert_eq!(&vec, new);
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = Vec::from(vec![1, 2, 3, 4, 5]);
+        let new = Vec::from(vec![1, 9, 3, 5, 6, 7]);
+
+        let patch = new.diff(&old);
+
+        assert_eq!(
+            patch.edits(),
+            [
+                VecEdit::Replace {
+                    index: 1,
+                    values: Vec::from(vec![9])
+                },
+                VecEdit::Remove { index: 3, len: 1 },
+                VecEdit::Insert {
+                    index: 4,
+                    values: Vec::from(vec![6, 7])
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_unchanged() {
+        let vec = Vec::from(vec![1, 2, 3]);
+
+        assert!(vec.diff(&vec).is_empty());
+        assert_eq!(vec.diff(&vec), VecPatch::new());
+    }
+
+    #[test]
+    fn test_diff_append() {
+        let old = Vec::from(vec!["a", "b"]);
+        let new = Vec::from(vec!["a", "b", "c"]);
+
+        assert_eq!(
+            new.diff(&old).edits(),
+            [VecEdit::Insert {
+                index: 2,
+                values: Vec::from(vec!["c"])
+            }]
+        );
+    }
+
+    #[test]
+    fn test_apply_round_trip() {
+        let empty: Vec<i32> = Vec::new();
+        let old = Vec::from(vec![1, 2, 3, 4, 5]);
+        let new = Vec::from(vec![0, 2, 4, 4, 6]);
+
+        round_trip(&old, &new);
+        round_trip(&new, &old);
+        round_trip(&empty, &new);
+        round_trip(&old, &empty);
+    }
+
+    #[test]
+    fn test_apply_randomized() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..1000 {
+            let old = sequence(&mut rng);
+            let new = sequence(&mut rng);
+            round_trip(&old, &new);
+        }
+    }
+
+    #[test]
+    fn test_diff_is_minimal() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        for _ in 0..200 {
+            let old = sequence(&mut rng);
+            let new = sequence(&mut rng);
+
+            // The number of changed elements is that of the longest common
+            // subsequence.
+            let mut lcs = vec![vec![0; new.len() + 1]; old.len() + 1];
+            for i in 0..old.len() {
+                for j in 0..new.len() {
+                    lcs[i + 1][j + 1] = if old[i] == new[j] {
+                        lcs[i][j] + 1
+                    } else {
+                        lcs[i][j + 1].max(lcs[i + 1][j])
+                    };
+                }
+            }
+            let common = lcs[old.len()][new.len()];
+
+            let changed: usize = new
+                .diff(&old)
+                .edits()
+                .iter()
+                .map(|edit| match edit {
+                    VecEdit::Insert { values, .. } => values.len(),
+                    VecEdit::Remove { len, .. } => *len,
+                    VecEdit::Replace { values, .. } => 2 * values.len(),
+                })
+                .sum();
+            assert_eq!(changed, old.len() + new.len() - 2 * common);
+        }
+    }
+
+    #[test]
+    fn test_try_apply_invalid() {
+        let mut vec = Vec::from(vec![1, 2, 3]);
+        let patches = [
+            VecPatch::from(vec![VecEdit::Remove { index: 2, len: 2 }]),
+            VecPatch::from(vec![VecEdit::Insert {
+                index: 4,
+                values: Vec::from(vec![4]),
+            }]),
+            VecPatch::from(vec![VecEdit::Replace {
+                index: 3,
+                values: Vec::from(vec![4]),
+            }]),
+            VecPatch::from(vec![VecEdit::Remove {
+                index: usize::MAX,
+                len: 2,
+            }]),
+        ];
+        for patch in patches {
+            assert_eq!(vec.try_apply(&patch), Err(PatchError { edit: 0, len: 3 }));
+        }
+
+        // The vector is left unchanged when a later edit fails.
+        let patch = VecPatch::from(vec![
+            VecEdit::Remove { index: 0, len: 1 },
+            VecEdit::Remove { index: 0, len: 3 },
+        ]);
+        assert_eq!(vec.try_apply(&patch), Err(PatchError { edit: 1, len: 2 }));
+        assert_eq!(vec, [1, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "edit 0 does not fit a vector of length 3")]
+    fn test_apply_invalid() {
+        let mut vec = Vec::from(vec![1, 2, 3]);
+        vec.apply(&VecPatch::from(vec![VecEdit::Remove { index: 3, len: 1 }]));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let mut rng = Rng(0xdead_beef);
+        for _ in 0..100 {
+            let old = sequence(&mut rng);
+            let new = sequence(&mut rng);
+            let patch = new.diff(&old);
+
+            let json = serde_json::to_string(&patch).unwrap();
+            let decoded: VecPatch<u8> = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded, patch);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_invalid() {
+        let decoded = serde_json::from_str::<VecPatch<u8>>("[[0, 2, [1]]]");
+        let negative = serde_json::from_str::<VecPatch<u8>>("[[-1, 0, [1]]]");
+
+        assert!(decoded.is_err());
+        assert!(negative.is_err());
+    }
+}
@@ -0,0 +1,490 @@
+//! Versioned save data that is upgraded to the current schema when loaded.
+//!
+//! Data is saved inside a [`Versioned`] envelope, the `Dictionary`
+//! `{"version": 2, "data": ...}`. When the shape of the data changes, bump
+//! the version and register a step that upgrades the previous shape. Steps
+//! work on [`Value`] trees, so they can rename keys and restructure data the
+//! current types no longer accept. Loading runs every step from the saved
+//! version up before converting the data:
+//!
+//! ```
+//! use gdvariants::{
+//!     collections::HashMap,
+//!     value::ToValue,
+//!     versioned::{Migrations, Versioned},
+//! };
+//!
+//! // Version 1 stored the gold as a number, version 2 in a "gold" key.
+//! let migrations = Migrations::new(2).step(1, |gold| {
+//!     Ok(HashMap::from([("gold", gold)]).to_value())
+//! });
+//!
+//! let saved = Versioned::new(1, 250).to_value();
+//! let inventory: HashMap<String, i64> = migrations.from_value(&saved).unwrap();
+//!
+//! assert_eq!(inventory["gold"], 250);
+//! ```
+//!
+//! Data written with [`value::serde`](crate::value::serde) is upgraded with
+//! [`Migrations::migrate`] before it is deserialized.
+
+use std::{collections::BTreeMap, error::Error, fmt};
+
+use gdnative::{
+    core_types::{Dictionary, VariantType},
+    prelude::{FromVariant, FromVariantError, OwnedToVariant, ToVariant, Variant},
+};
+
+use crate::value::{FromValue, FromValueError, ResourceError, ToValue, Value};
+
+const VERSION: &str = "version";
+const DATA: &str = "data";
+
+/// Data tagged with the version of its schema.
+///
+/// Converts to and from the `Dictionary` `{"version": version, "data": data}`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Versioned<T> {
+    pub version: u32,
+    pub data: T,
+}
+
+impl<T> Versioned<T> {
+    /// Tags `data` with `version`.
+    #[inline]
+    pub fn new(version: u32, data: T) -> Versioned<T> {
+        Versioned { version, data }
+    }
+}
+
+impl<T> ToValue for Versioned<T>
+where
+    T: ToValue,
+{
+    fn to_value(&self) -> Value {
+        Value::Dictionary(vec![
+            (VERSION.to_value(), self.version.to_value()),
+            (DATA.to_value(), self.data.to_value()),
+        ])
+    }
+}
+
+impl<T> FromValue for Versioned<T>
+where
+    T: FromValue,
+{
+    fn from_value(value: &Value) -> Result<Self, FromValueError> {
+        if value.as_dictionary().is_none() {
+            return Err(FromValueError::invalid_type(VariantType::Dictionary, value));
+        }
+        let field = |key: &str| {
+            value
+                .get(&key.to_value())
+                .ok_or_else(|| FromValueError::Custom(format!("missing key {:?}", key)))
+        };
+        Ok(Versioned {
+            version: u32::from_value(field(VERSION)?)?,
+            data: T::from_value(field(DATA)?)?,
+        })
+    }
+}
+
+impl<T> ToVariant for Versioned<T>
+where
+    T: ToVariant,
+{
+    fn to_variant(&self) -> Variant {
+        let dictionary = Dictionary::new();
+        dictionary.insert(VERSION, self.version);
+        dictionary.insert(DATA, self.data.to_variant());
+        dictionary.owned_to_variant()
+    }
+}
+
+impl<T> FromVariant for Versioned<T>
+where
+    T: FromVariant,
+{
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        let dictionary = Dictionary::from_variant(variant)?;
+        let field = |key: &'static str| {
+            dictionary.get(key).ok_or(FromVariantError::InvalidField {
+                field_name: key,
+                error: Box::new(FromVariantError::InvalidNil),
+            })
+        };
+        Ok(Versioned {
+            version: u32::from_variant(&field(VERSION)?)?,
+            data: T::from_variant(&field(DATA)?)?,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for Versioned<T>
+where
+    T: serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("Versioned", 2)?;
+        state.serialize_field(VERSION, &self.version)?;
+        state.serialize_field(DATA, &self.data)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for Versioned<T>
+where
+    T: serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use std::marker::PhantomData;
+
+        use serde::de::{Error, MapAccess, Visitor};
+
+        struct VersionedVisitor<T>(PhantomData<T>);
+
+        impl<'de, T> Visitor<'de> for VersionedVisitor<T>
+        where
+            T: serde::Deserialize<'de>,
+        {
+            type Value = Versioned<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map with a version and data")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Versioned<T>, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut version = None;
+                let mut data = None;
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        VERSION => version = Some(map.next_value()?),
+                        DATA => data = Some(map.next_value()?),
+                        _ => {
+                            map.next_value::<serde::de::IgnoredAny>()?;
+                        }
+                    }
+                }
+                Ok(Versioned {
+                    version: version.ok_or_else(|| A::Error::missing_field(VERSION))?,
+                    data: data.ok_or_else(|| A::Error::missing_field(DATA))?,
+                })
+            }
+        }
+
+        deserializer.deserialize_struct(
+            "Versioned",
+            &[VERSION, DATA],
+            VersionedVisitor(PhantomData),
+        )
+    }
+}
+
+/// Error returned when versioned data cannot be loaded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MigrationError {
+    /// The data was saved with a newer version than the current one.
+    TooNew { version: u32, current: u32 },
+    /// No step upgrades data from `version`.
+    MissingStep { version: u32 },
+    /// The step upgrading data from `version` failed.
+    Step { version: u32, error: FromValueError },
+    /// The envelope or the upgraded data could not be converted.
+    Value(FromValueError),
+    /// The upgraded data could not be converted from a `Variant`.
+    Variant(FromVariantError),
+    /// The upgraded data holds a resource reference, which cannot be
+    /// converted to a `Variant`.
+    Resource(ResourceError),
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationError::TooNew { version, current } => write!(
+                f,
+                "data has version {}, newer than the current version {}",
+                version, current
+            ),
+            MigrationError::MissingStep { version } => {
+                write!(f, "no migration from version {}", version)
+            }
+            MigrationError::Step { version, error } => {
+                write!(f, "migration from version {}: {}", version, error)
+            }
+            MigrationError::Value(error) => error.fmt(f),
+            MigrationError::Variant(error) => error.fmt(f),
+            MigrationError::Resource(error) => error.fmt(f),
+        }
+    }
+}
+
+impl Error for MigrationError {}
+
+impl From<FromValueError> for MigrationError {
+    fn from(error: FromValueError) -> MigrationError {
+        MigrationError::Value(error)
+    }
+}
+
+type Step = Box<dyn Fn(Value) -> Result<Value, FromValueError> + Send + Sync>;
+
+/// The steps that upgrade versioned data to the current version.
+///
+/// A step registered for version `n` turns data of version `n` into data of
+/// version `n + 1`. Data saved at the current version is loaded as is.
+pub struct Migrations {
+    version: u32,
+    steps: BTreeMap<u32, Step>,
+}
+
+impl Migrations {
+    /// Creates a registry for data whose current version is `version`.
+    #[inline]
+    pub fn new(version: u32) -> Migrations {
+        Migrations {
+            version,
+            steps: BTreeMap::new(),
+        }
+    }
+
+    /// Registers the step that upgrades data from `version` to
+    /// `version + 1`, replacing any previous one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `version` is not older than the current version.
+    pub fn step<F>(mut self, version: u32, f: F) -> Migrations
+    where
+        F: Fn(Value) -> Result<Value, FromValueError> + Send + Sync + 'static,
+    {
+        assert!(
+            version < self.version,
+            "migration from version {} is not older than the current version {}",
+            version,
+            self.version
+        );
+        self.steps.insert(version, Box::new(f));
+        self
+    }
+
+    /// Returns the current version.
+    #[inline]
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Upgrades `data` from `version` to the current version, one step at a
+    /// time.
+    pub fn upgrade(&self, version: u32, mut data: Value) -> Result<Value, MigrationError> {
+        if version > self.version {
+            return Err(MigrationError::TooNew {
+                version,
+                current: self.version,
+            });
+        }
+        for version in version..self.version {
+            let step = self
+                .steps
+                .get(&version)
+                .ok_or(MigrationError::MissingStep { version })?;
+            data = step(data).map_err(|error| MigrationError::Step { version, error })?;
+        }
+        Ok(data)
+    }
+
+    /// Reads a [`Versioned`] envelope and returns its data, upgraded to the
+    /// current version.
+    pub fn migrate(&self, saved: &Value) -> Result<Value, MigrationError> {
+        let saved = Versioned::<Value>::from_value(saved)?;
+        self.upgrade(saved.version, saved.data)
+    }
+
+    /// Reads a [`Versioned`] envelope and converts its data once it is
+    /// upgraded.
+    pub fn from_value<T>(&self, saved: &Value) -> Result<T, MigrationError>
+    where
+        T: FromValue,
+    {
+        Ok(T::from_value(&self.migrate(saved)?)?)
+    }
+
+    /// Reads a `Variant` holding a [`Versioned`] envelope and converts its
+    /// data once it is upgraded.
+    ///
+    /// Data that is already at the current version is converted directly.
+    pub fn from_variant<T>(&self, saved: &Variant) -> Result<T, MigrationError>
+    where
+        T: FromVariant,
+    {
+        let saved = Versioned::<Variant>::from_variant(saved).map_err(MigrationError::Variant)?;
+        let data = if saved.version == self.version {
+            saved.data
+        } else {
+            let data = Value::from_variant(&saved.data).map_err(MigrationError::Variant)?;
+            let data = self.upgrade(saved.version, data)?;
+            Variant::try_from(&data).map_err(MigrationError::Resource)?
+        };
+        T::from_variant(&data).map_err(MigrationError::Variant)
+    }
+
+    /// Wraps `data` in a [`Versioned`] envelope with the current version.
+    pub fn to_value<T>(&self, data: &T) -> Value
+    where
+        T: ToValue + ?Sized,
+    {
+        Versioned::new(self.version, data.to_value()).to_value()
+    }
+
+    /// Wraps `data` in a [`Versioned`] envelope with the current version.
+    pub fn to_variant<T>(&self, data: &T) -> Variant
+    where
+        T: ToVariant + ?Sized,
+    {
+        Versioned::new(self.version, data.to_variant()).to_variant()
+    }
+}
+
+impl fmt::Debug for Migrations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Migrations")
+            .field("version", &self.version)
+            .field("steps", &self.steps.keys().collect::<std::vec::Vec<_>>())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        collections::HashMap,
+        value::{FromValue, FromValueError, ToValue, Value},
+        vec::Vec,
+        versioned::{MigrationError, Migrations, Versioned},
+    };
+
+    /// Version 1 is a list of item names, version 2 counts each item, version
+    /// 3 renames "potion" to "health_potion".
+    fn migrations() -> Migrations {
+        Migrations::new(3)
+            .step(1, |items| {
+                let mut counts: HashMap<String, i64> = HashMap::new();
+                for item in Vec::<String>::from_value(&items)? {
+                    *counts.entry(item).or_insert(0) += 1;
+                }
+                Ok(counts.to_value())
+            })
+            .step(2, |counts| {
+                let mut counts = HashMap::<String, i64>::from_value(&counts)?;
+                if let Some(count) = counts.remove("potion") {
+                    counts.insert("health_potion".to_string(), count);
+                }
+                Ok(counts.to_value())
+            })
+    }
+
+    #[test]
+    fn test_versioned_value() {
+        let versioned = Versioned::new(2, Vec::from(vec![1, 2]));
+        let value = versioned.to_value();
+
+        assert_eq!(value.get(&"version".to_value()), Some(&Value::Int(2)));
+        assert_eq!(Versioned::from_value(&value), Ok(versioned));
+        assert!(Versioned::<i64>::from_value(&Value::Int(1)).is_err());
+    }
+
+    #[test]
+    fn test_migrate_multiple_steps() {
+        let saved = Versioned::new(1, vec!["potion", "sword", "potion"]).to_value();
+
+        let items: HashMap<String, i64> = migrations().from_value(&saved).unwrap();
+
+        assert_eq!(
+            items,
+            HashMap::from([("health_potion".to_string(), 2), ("sword".to_string(), 1)])
+        );
+    }
+
+    #[test]
+    fn test_migrate_single_step() {
+        let saved = Versioned::new(2, HashMap::from([("potion", 3)])).to_value();
+
+        let items: HashMap<String, i64> = migrations().from_value(&saved).unwrap();
+
+        assert_eq!(items, HashMap::from([("health_potion".to_string(), 3)]));
+    }
+
+    #[test]
+    fn test_current_version() {
+        let migrations = migrations();
+        let saved = migrations.to_value(&HashMap::from([("potion", 3)]));
+
+        let items: HashMap<String, i64> = migrations.from_value(&saved).unwrap();
+
+        assert_eq!(items, HashMap::from([("potion".to_string(), 3)]));
+    }
+
+    #[test]
+    fn test_errors() {
+        let migrations = migrations();
+
+        assert_eq!(
+            migrations.upgrade(4, Value::Nil),
+            Err(MigrationError::TooNew {
+                version: 4,
+                current: 3
+            })
+        );
+        assert_eq!(
+            migrations.upgrade(0, Value::Nil),
+            Err(MigrationError::MissingStep { version: 0 })
+        );
+        assert!(matches!(
+            migrations.upgrade(1, Value::Int(1)),
+            Err(MigrationError::Step { version: 1, .. })
+        ));
+        assert!(matches!(
+            migrations.migrate(&Value::Dictionary(vec![])),
+            Err(MigrationError::Value(FromValueError::Custom(_)))
+        ));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_step_not_older() {
+        let _ = Migrations::new(2).step(2, Ok);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        use crate::value::serde::{from_value, to_value};
+
+        let versioned = Versioned::new(1, vec!["potion".to_string()]);
+
+        let json = serde_json::to_string(&versioned).unwrap();
+        assert_eq!(json, r#"{"version":1,"data":["potion"]}"#);
+        assert_eq!(
+            serde_json::from_str::<Versioned<std::vec::Vec<String>>>(&json).unwrap(),
+            versioned
+        );
+
+        let saved = to_value(&versioned).unwrap();
+        let items: HashMap<String, i64> =
+            from_value(&migrations().migrate(&saved).unwrap()).unwrap();
+        assert_eq!(items, HashMap::from([("health_potion".to_string(), 1)]));
+    }
+}
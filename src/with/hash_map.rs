@@ -0,0 +1,44 @@
+//! Converts a `std::collections::HashMap` the same way as
+//! [`crate::collections::HashMap`].
+
+use std::hash::{BuildHasher, Hash};
+
+use gdnative::{
+    core_types::Dictionary,
+    prelude::{FromVariant, FromVariantError, OwnedToVariant, ToVariant, ToVariantEq, Variant},
+};
+
+/// Converts the entries into a `Variant` holding a `Dictionary`.
+pub fn to_variant<K, V, S>(map: &std::collections::HashMap<K, V, S>) -> Variant
+where
+    K: ToVariantEq + ToVariant,
+    V: ToVariant,
+{
+    let dictionary = Dictionary::new();
+    for (key, value) in map {
+        dictionary.insert(key, value);
+    }
+    dictionary.owned_to_variant()
+}
+
+/// Reads a `Variant` holding a `Dictionary`.
+pub fn from_variant<K, V, S>(
+    variant: &Variant,
+) -> Result<std::collections::HashMap<K, V, S>, FromVariantError>
+where
+    K: Hash + Eq + FromVariant,
+    V: FromVariant,
+    S: BuildHasher + Default,
+{
+    let dictionary = Dictionary::from_variant(variant)?;
+    let mut hash_map = std::collections::HashMap::with_capacity_and_hasher(
+        dictionary.len() as usize,
+        S::default(),
+    );
+    for (variant_key, variant_value) in dictionary.iter() {
+        let key = K::from_variant(&variant_key)?;
+        let value = V::from_variant(&variant_value)?;
+        hash_map.insert(key, value);
+    }
+    Ok(hash_map)
+}
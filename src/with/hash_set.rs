@@ -0,0 +1,39 @@
+//! Converts a `std::collections::HashSet` the same way as
+//! [`crate::collections::HashSet`].
+
+use std::hash::{BuildHasher, Hash};
+
+use gdnative::{
+    core_types::VariantArray,
+    prelude::{FromVariant, FromVariantError, OwnedToVariant, ToVariant, Variant},
+};
+
+/// Converts the elements into a `Variant` holding an `Array`.
+pub fn to_variant<T, S>(set: &std::collections::HashSet<T, S>) -> Variant
+where
+    T: ToVariant,
+{
+    let array = VariantArray::new();
+    for value in set {
+        array.push(value.to_variant());
+    }
+    array.owned_to_variant()
+}
+
+/// Reads a `Variant` holding an `Array`. Duplicate elements are merged.
+pub fn from_variant<T, S>(
+    variant: &Variant,
+) -> Result<std::collections::HashSet<T, S>, FromVariantError>
+where
+    T: Eq + Hash + FromVariant,
+    S: BuildHasher + Default,
+{
+    let array = VariantArray::from_variant(variant)?;
+    let mut hash_set =
+        std::collections::HashSet::with_capacity_and_hasher(array.len() as usize, S::default());
+    for variant_value in array.iter() {
+        let value = T::from_variant(&variant_value)?;
+        hash_set.insert(value);
+    }
+    Ok(hash_set)
+}
@@ -0,0 +1,22 @@
+//! Conversion modules for std collections in derived types.
+//!
+//! `#[derive(ToVariant, FromVariant)]` accepts `#[variant(with = "...")]` to
+//! convert a field with the `to_variant` and `from_variant` functions of a
+//! module. The modules here convert the std collections the same way as the
+//! gdvariants collections, so domain types can keep their std fields:
+//!
+//! ```ignore
+//! use gdnative::prelude::*;
+//!
+//! #[derive(ToVariant, FromVariant)]
+//! struct Inventory {
+//!     #[variant(with = "gdvariants::with::hash_map")]
+//!     items: std::collections::HashMap<i64, i64>,
+//!     #[variant(with = "gdvariants::with::vec")]
+//!     slots: std::vec::Vec<String>,
+//! }
+//! ```
+
+pub mod hash_map;
+pub mod hash_set;
+pub mod vec;
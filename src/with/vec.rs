@@ -0,0 +1,32 @@
+//! Converts a `std::vec::Vec` the same way as [`crate::vec::Vec`].
+
+use gdnative::{
+    core_types::VariantArray,
+    prelude::{FromVariant, FromVariantError, OwnedToVariant, ToVariant, Variant},
+};
+
+/// Converts the elements into a `Variant` holding an `Array`.
+pub fn to_variant<T>(vec: &[T]) -> Variant
+where
+    T: ToVariant,
+{
+    let array = VariantArray::new();
+    for value in vec {
+        array.push(value.to_variant());
+    }
+    array.owned_to_variant()
+}
+
+/// Reads a `Variant` holding an `Array`.
+pub fn from_variant<T>(variant: &Variant) -> Result<std::vec::Vec<T>, FromVariantError>
+where
+    T: FromVariant,
+{
+    let array = VariantArray::from_variant(variant)?;
+    let mut vec = std::vec::Vec::with_capacity(array.len() as usize);
+    for variant in array.iter() {
+        let value = T::from_variant(&variant)?;
+        vec.push(value);
+    }
+    Ok(vec)
+}
@@ -20,6 +20,8 @@ pub struct Source {
 #[serde(rename_all = "camelCase")]
 pub struct Docs {
     pub std: String,
+    #[serde(default)]
+    pub nightly: Option<String>,
     pub local: String,
     pub overwrites: Vec<Overwrite>,
 }
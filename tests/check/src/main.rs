@@ -22,7 +22,12 @@ enum Commands {
     /// Get API for source
     GetApi { name: String, source: String },
     /// Check if crate implements the std library API
-    Implements { name: String },
+    Implements {
+        name: String,
+        /// Compare against the nightly std docs, for the `nightly` feature
+        #[clap(long)]
+        nightly: bool,
+    },
 }
 
 fn get_doc_source<'a>(info: &'a Info, source: &str, name: &str) -> &'a str {
@@ -35,6 +40,15 @@ fn get_doc_source<'a>(info: &'a Info, source: &str, name: &str) -> &'a str {
             .docs
             .std
             .as_str(),
+        "nightly" => info
+            .sources
+            .iter()
+            .find(|x| x.name == name)
+            .unwrap()
+            .docs
+            .nightly
+            .as_deref()
+            .expect("No nightly docs in info file"),
         "local" => info
             .sources
             .iter()
@@ -184,7 +198,7 @@ fn main() {
             let api = get_api(&info, &name, &source);
             println!("{}", api);
         }
-        Commands::Implements { name } => {
+        Commands::Implements { name, nightly } => {
             let info = info::get_info("./info.json");
             let local_api = get_api(&info, &name, "local");
             let std_api = apply(
@@ -195,7 +209,7 @@ fn main() {
                     .unwrap()
                     .docs
                     .overwrites,
-                &get_api(&info, &name, "std"),
+                &get_api(&info, &name, if nightly { "nightly" } else { "std" }),
             );
             if implements(&local_api, &std_api) == false {
                 std::process::exit(1);